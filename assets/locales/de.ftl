@@ -0,0 +1,184 @@
+## Main menu
+
+title = Dinosaurierspiel
+one-player = 1 Spieler
+two-players = 2 Spieler
+against-autopilot = gegen den Autopiloten
+best = Rekord: { $score }
+best-with-assists = Rekord mit Hilfen: { $score }
+assists-are-on = Hilfen sind an, daher zählen Läufe nicht für den Rekord.
+play = Spielen!
+assists = Hilfen
+settings = Einstellungen
+credits = Mitwirkende
+go-back = Zurück
+recent-runs = Letzte Läufe
+assisted = mit Hilfen
+
+mode-classic = Klassisch
+mode-classic-description = Spring so lange wie möglich über die Kakteen.
+mode-zen = Zen
+mode-zen-description = Nichts kann dir schaden, schau einfach, wie weit du kommst.
+mode-time-attack = Zeitrennen
+mode-time-attack-description = Komm in 60 Sekunden so weit wie möglich.
+mode-daily = Tägliche Herausforderung
+mode-daily-description = Heute bekommen alle dieselbe Strecke. Nur dein erster Lauf des Tages zählt.
+
+daily-official-score = Heutige offizielle Punktzahl: { $score }. Weitere Läufe heute sind Übung.
+daily-next-run-official = Dein nächster Lauf ist der offizielle Versuch für heute.
+daily-needs-standard-rules = Schalte Hilfen und Power-ups aus, um den heutigen offiziellen Versuch zu spielen.
+daily-leaderboard = Tagesbestenliste
+
+race-online = Online-Rennen
+relay = Relay-Server
+room = Raum
+room-empty = Wähle einen Raum, in dem du deinen Gegner triffst.
+online-uses-classic-rules = Online-Rennen nutzen immer die klassischen Regeln.
+find-opponent = Gegner suchen
+waiting-for-opponent = Warte auf einen Gegner in Raum { $room }
+cancel = Abbrechen
+
+credits-made-by = Gemacht von { $author }
+credits-thanks = Vielen Dank an die Online-Quellen, die dieses Programm möglich gemacht haben.
+powered-by = Läuft mit
+powered-by-and = und
+source-code = Quellcode.
+file = Datei
+quit = Beenden
+
+## Assists
+
+assists-kept-apart = Läufe mit Hilfen werden getrennt von deinen Rekorden gespeichert.
+one-switch = Spielen mit einer Taste
+one-switch-hint = Jede Taste springt vom Boden und lässt in der Luft wieder fallen
+auto-jump = Automatisch springen
+auto-jump-hint = Über Hindernisse wird für dich gesprungen. Du kannst trotzdem selbst springen, ducken und fallen.
+game-speed = Spieltempo (%)
+extra-jump-buffer = Zusätzlicher Sprungpuffer (Ticks)
+extra-jump-buffer-hint = So viel früher gedrückte Sprünge passieren trotzdem bei der Landung
+assists-off = Alle Hilfen ausschalten
+
+## Settings
+
+language = Sprache
+language-system = System: { $language }
+jump-buffer = Sprungpuffer (Ticks)
+coyote-time = Kojotenzeit (Ticks)
+forgiveness-hint = Eine Änderung gegenüber dem Standard zählt als Hilfe
+power-ups = Power-ups
+power-ups-hint = Für das klassische Spiel auslassen. Läufe mit Power-ups setzen keine Bestleistung
+input-delay = Online-Eingabeverzögerung (Ticks)
+input-delay-hint = Erhöhen, wenn Online-Rennen ruckeln
+keys = Tasten
+keys-hint = Klicke einen Knopf an und drücke dann die neue Taste. Escape behält die alten Tasten.
+keys-alone = Allein spielen
+key-jump = Springen
+key-duck = Ducken
+keys-waiting = Drücke eine Taste…
+attract-mode = Autopilot-Demo im Hauptmenü
+field-theme = Farben des Spielfelds
+theme-classic = Klassisch
+theme-dark = Dunkel
+theme-high-contrast = Hoher Kontrast
+theme-custom = Eigene
+theme-sprites = Figuren
+theme-background = Hintergrund
+effects = Effekte
+effects-hint = Partikel und Bildschirmwackeln. Aus eignet sich für weniger Bewegung
+effects-off = Aus
+effects-low = Wenig
+effects-full = Voll
+narrate-obstacles = Hindernisse ansagen
+narrate-obstacles-hint = Screenreader sagen, was kommt und wie bald
+play-intro = Intro abspielen
+play-intro-hint = Enter überspringt es während eines Laufs
+clip-length = Cliplänge (Sekunden)
+clip-length-hint = 0 speichert den ganzen Lauf, egal wie lang
+clip-format = Clipformat
+clip-format-gif = Animiertes GIF
+clip-format-png-sequence = PNG-Folge
+reset-settings = Auf Standard zurücksetzen
+
+## Playing
+
+game-heading = Dino-Spiel
+play-field = Spielfeld
+lane-label = S{ $player }
+ready = Bereit?
+ready-player-one = Spieler 1 springt mit w oder Leertaste und duckt sich mit s.
+ready-player-two = Spieler 2 springt mit Pfeil nach oben und duckt sich mit Pfeil nach unten.
+ready-start = Klicke oder drücke Leertaste, w oder Pfeil nach oben zum Starten.
+ready-skip-intro = Drücke Enter, um das Intro zu überspringen.
+finish-run = Lauf beenden
+
+## After a run
+
+run-finished = Lauf beendet, noch einmal?
+times-up = Die Zeit ist um, noch einmal?
+you-died = Du bist gestorben, noch einmal?
+assisted-run = Hilfen waren an, daher wird dieser Lauf getrennt von deinem Rekord gespeichert.
+power-ups-run = Power-ups waren an, deshalb zählt dieser Lauf nicht für deine Bestleistung.
+practice-run = Das war ein Übungslauf, nur der erste Lauf des Tages zählt.
+play-again = Noch einmal
+main-menu = Zurück zum Hauptmenü
+copy-replay = Wiederholung kopieren
+copy-replay-hint = Prüfe sie mit dem verify-Befehl des headless-Programms
+save-clip = Clip speichern
+clip-saving = Clip wird gespeichert …
+clip-saved = { $files } gespeichert
+clip-failed = Der Clip konnte nicht gespeichert werden: { $error }
+you = Du
+player = Spieler { $player }
+you-win = Du gewinnst!
+player-wins = { $player } gewinnt!
+draw = Unentschieden!
+player-score = { $player }: { $score }
+
+## Screen readers
+
+narration-game-over = Spiel vorbei. Punktzahl { $score }.
+narration-milestone = { $score } Punkte.
+narration-high-score = Neuer Rekord!
+narration-obstacle = { $obstacle } in { $seconds } Sekunden.
+obstacle-small-cactus = Kleiner Kaktus
+obstacle-wide-cactus = Breiter Kaktus
+
+## Moving progress
+
+save-data = Fortschritt übertragen
+save-data-hint = Nimm deine Rekorde, deinen Verlauf und deine Einstellungen auf ein anderes Gerät oder in einen anderen Browser mit.
+copy-save-code = Speichercode kopieren
+save-code-copied = Speichercode kopiert.
+export-save = In eine Datei exportieren
+save-exported = { $file } gespeichert
+save-export-failed = Der Spielstand konnte nicht exportiert werden: { $error }
+save-code-hint = Füge einen Speichercode ein oder ziehe eine Speicherdatei ins Fenster
+import-save = Importieren
+import-save-hint = Ersetzt deinen Fortschritt hier
+save-imported = Fortschritt importiert.
+save-import-failed = Der Spielstand konnte nicht importiert werden: { $error }
+
+## Starting up
+
+startup-save-failed = Ein Teil des gespeicherten Fortschritts konnte nicht geladen werden. Er bleibt, wie er ist, bis du ihn unter Profile neu beginnst oder löschst: { $error }
+startup-profiles-failed = Die Profilliste konnte nicht geladen werden, daher wird nichts gespeichert, bis du unter Profile neu beginnst: { $error }
+startup-asset-map-failed = Die Asset-Map konnte nicht geladen werden, daher wird die eingebaute verwendet: { $error }
+dismiss = Schließen
+
+## Profiles
+
+profile = Profil
+manage-profiles = Profile
+profiles = Profile
+profiles-hint = Alle, die diesen Computer teilen, können eigene Rekorde, einen eigenen Verlauf und eigene Einstellungen haben.
+rename = Umbenennen
+delete = Löschen
+delete-profile-confirm = { $name } mit dem ganzen Fortschritt löschen?
+profile-name = Name
+create-profile = Profil hinzufügen
+profile-name-empty = Ein Profil braucht einen Namen.
+profile-name-too-long = Dieser Name ist zu lang.
+profile-name-taken = Es gibt schon ein Profil mit diesem Namen.
+profiles-damaged = Die Profilliste konnte nicht geladen werden, daher wird hier nichts gespeichert.
+profile-damaged = Beschädigt
+start-over = Neu beginnen
@@ -0,0 +1,186 @@
+# Every other language has to have each of these messages, which tests/locale.rs checks.
+
+## Main menu
+
+title = Dinosaur game
+one-player = 1 player
+two-players = 2 players
+against-autopilot = against the autopilot
+best = Best: { $score }
+best-with-assists = Best with assists: { $score }
+assists-are-on = Assists are on, so runs won't set a best score.
+play = Play!
+assists = Assists
+settings = Settings
+credits = Credits
+go-back = Go back
+recent-runs = Recent runs
+assisted = assisted
+
+mode-classic = Classic
+mode-classic-description = Jump over the cacti for as long as you can.
+mode-zen = Zen
+mode-zen-description = Nothing can hurt you, just see how far you go.
+mode-time-attack = Time attack
+mode-time-attack-description = Go as far as you can in 60 seconds.
+mode-daily = Daily challenge
+mode-daily-description = Everyone gets the same course today. Only your first run of the day counts.
+
+daily-official-score = Today's official score: { $score }. Any more runs today are practice.
+daily-next-run-official = Your next run is today's official attempt.
+daily-needs-standard-rules = Turn off assists and power-ups to play today's official attempt.
+daily-leaderboard = Daily leaderboard
+
+race-online = Race online
+relay = Relay
+room = Room
+room-empty = Pick a room to meet your opponent in.
+online-uses-classic-rules = Online races always use the classic rules.
+find-opponent = Find an opponent
+waiting-for-opponent = Waiting for an opponent in room { $room }
+cancel = Cancel
+
+credits-made-by = Made by { $author }
+credits-thanks = Many thanks to the online resources that made this program possible.
+powered-by = Powered by
+powered-by-and = and
+source-code = Source code.
+file = File
+quit = Quit
+
+## Assists
+
+assists-kept-apart = Runs played with any assist on are kept apart from your best scores.
+one-switch = One-switch play
+one-switch-hint = Any button jumps from the floor and drops back down in the air
+auto-jump = Auto-jump
+auto-jump-hint = Obstacles are jumped over for you. You can still jump, duck and drop yourself.
+game-speed = Game speed (%)
+extra-jump-buffer = Extra jump buffer (ticks)
+extra-jump-buffer-hint = Jumps pressed this much earlier still happen on landing
+assists-off = Turn all assists off
+
+## Settings
+
+language = Language
+language-system = System: { $language }
+jump-buffer = Jump buffer (ticks)
+coyote-time = Coyote time (ticks)
+forgiveness-hint = Changing this from the default counts as an assist
+power-ups = Power-ups
+power-ups-hint = Leave off for the classic game. Runs with power-ups don't set best scores
+input-delay = Online input delay (ticks)
+input-delay-hint = Raise this if online races stutter
+keys = Keys
+keys-hint = Click a button, then press the key to use instead. Escape keeps the old keys.
+keys-alone = Playing alone
+key-jump = Jump
+key-duck = Duck
+keys-waiting = Press a key…
+attract-mode = Autopilot demo on the main menu
+field-theme = Play field theme
+theme-classic = Classic
+theme-dark = Dark
+theme-high-contrast = High contrast
+theme-custom = Custom
+theme-sprites = Sprites
+theme-background = Background
+effects = Effects
+effects-hint = Particles and screen shake. Off suits reduced motion
+effects-off = Off
+effects-low = Low
+effects-full = Full
+narrate-obstacles = Announce obstacles
+narrate-obstacles-hint = Screen readers say what is coming up and how soon
+play-intro = Play the intro
+play-intro-hint = Enter skips it during a run
+clip-length = Clip length (seconds)
+clip-length-hint = 0 saves the whole run, however long it is
+clip-format = Clip format
+clip-format-gif = Animated GIF
+clip-format-png-sequence = PNG sequence
+reset-settings = Reset to defaults
+
+## Playing
+
+game-heading = Dino Game
+play-field = Play field
+lane-label = P{ $player }
+ready = Ready?
+ready-player-one = Player 1 jumps with w or space and ducks with s.
+ready-player-two = Player 2 jumps with the up arrow and ducks with the down arrow.
+ready-start = Click or press space, w or up arrow to start.
+ready-skip-intro = Press enter to skip the intro.
+finish-run = Finish run
+
+## After a run
+
+run-finished = Run finished, play again?
+times-up = Time's up, play again?
+you-died = You died, play again?
+assisted-run = Assists were on, so this run is kept apart from your best score.
+power-ups-run = Power-ups were on, so this run doesn't count toward your best score.
+practice-run = This was a practice run, only the first run of the day counts.
+play-again = Play again
+main-menu = Return to main menu
+copy-replay = Copy replay
+copy-replay-hint = Check it with the headless binary's verify command
+save-clip = Save clip
+clip-saving = Saving the clip…
+clip-saved = Saved { $files }
+clip-failed = Couldn't save the clip: { $error }
+you = You
+player = Player { $player }
+you-win = You win!
+player-wins = { $player } wins!
+draw = It's a draw!
+player-score = { $player }: { $score }
+
+## Screen readers
+
+narration-game-over = Game over. Score { $score }.
+narration-milestone = { $score } points.
+narration-high-score = New high score!
+narration-obstacle = { $obstacle } in { $seconds } seconds.
+obstacle-small-cactus = Small cactus
+obstacle-wide-cactus = Wide cactus
+
+## Moving progress
+
+save-data = Move your progress
+save-data-hint = Take your best scores, history and settings to another device or browser.
+copy-save-code = Copy save code
+save-code-copied = Copied the save code.
+export-save = Export to a file
+save-exported = Saved { $file }
+save-export-failed = Couldn't export the save: { $error }
+save-code-hint = Paste a save code, or drop a save file on the window
+import-save = Import
+import-save-hint = Replaces your progress here
+save-imported = Progress imported.
+save-import-failed = Couldn't import the save: { $error }
+
+## Starting up
+
+startup-save-failed = Couldn't load some saved progress. It is kept as it was until you start it over or delete it under Profiles: { $error }
+startup-profiles-failed = Couldn't load the list of profiles, so nothing is saved until you start over under Profiles: { $error }
+startup-asset-map-failed = Couldn't load the asset map, so the built in one is used: { $error }
+dismiss = Dismiss
+
+## Profiles
+
+profile = Profile
+manage-profiles = Profiles
+profiles = Profiles
+profiles-hint = Everyone sharing this computer can have their own best scores, history and settings.
+rename = Rename
+delete = Delete
+delete-profile-confirm = Delete { $name } and all their progress?
+profile-name = Name
+create-profile = Add profile
+profile-name-empty = A profile needs a name.
+profile-name-too-long = That name is too long.
+profile-name-taken = There is already a profile with that name.
+profiles-damaged = The list of profiles couldn't be loaded, so nothing here is being saved.
+profile-damaged = Damaged
+start-over = Start over
@@ -0,0 +1,184 @@
+## Main menu
+
+title = Juego del dinosaurio
+one-player = 1 jugador
+two-players = 2 jugadores
+against-autopilot = contra el piloto automático
+best = Récord: { $score }
+best-with-assists = Récord con ayudas: { $score }
+assists-are-on = Las ayudas están activadas, así que las partidas no marcarán récord.
+play = ¡Jugar!
+assists = Ayudas
+settings = Ajustes
+credits = Créditos
+go-back = Volver
+recent-runs = Partidas recientes
+assisted = con ayudas
+
+mode-classic = Clásico
+mode-classic-description = Salta los cactus durante todo el tiempo que puedas.
+mode-zen = Zen
+mode-zen-description = Nada puede hacerte daño, solo mira hasta dónde llegas.
+mode-time-attack = Contrarreloj
+mode-time-attack-description = Llega tan lejos como puedas en 60 segundos.
+mode-daily = Reto diario
+mode-daily-description = Hoy todos tienen el mismo recorrido. Solo cuenta tu primera partida del día.
+
+daily-official-score = Puntuación oficial de hoy: { $score }. Las demás partidas de hoy son de práctica.
+daily-next-run-official = Tu próxima partida es el intento oficial de hoy.
+daily-needs-standard-rules = Desactiva las ayudas y los potenciadores para jugar el intento oficial de hoy.
+daily-leaderboard = Clasificación diaria
+
+race-online = Carrera en línea
+relay = Servidor
+room = Sala
+room-empty = Elige una sala donde encontrarte con tu rival.
+online-uses-classic-rules = Las carreras en línea siempre usan las reglas clásicas.
+find-opponent = Buscar un rival
+waiting-for-opponent = Esperando a un rival en la sala { $room }
+cancel = Cancelar
+
+credits-made-by = Hecho por { $author }
+credits-thanks = Muchas gracias a los recursos en línea que hicieron posible este programa.
+powered-by = Funciona con
+powered-by-and = y
+source-code = Código fuente.
+file = Archivo
+quit = Salir
+
+## Assists
+
+assists-kept-apart = Las partidas jugadas con alguna ayuda se guardan aparte de tus récords.
+one-switch = Jugar con un solo botón
+one-switch-hint = Cualquier botón salta desde el suelo y baja de nuevo en el aire
+auto-jump = Salto automático
+auto-jump-hint = Los obstáculos se saltan solos. Aún puedes saltar, agacharte y bajar tú.
+game-speed = Velocidad del juego (%)
+extra-jump-buffer = Margen de salto extra (ticks)
+extra-jump-buffer-hint = Los saltos pulsados con esta antelación aún ocurren al aterrizar
+assists-off = Desactivar todas las ayudas
+
+## Settings
+
+language = Idioma
+language-system = Sistema: { $language }
+jump-buffer = Margen de salto (ticks)
+coyote-time = Tiempo de coyote (ticks)
+forgiveness-hint = Cambiarlo respecto al valor predeterminado cuenta como ayuda
+power-ups = Potenciadores
+power-ups-hint = Desactívalos para el juego clásico. Las partidas con potenciadores no marcan récords
+input-delay = Retraso de entrada en línea (ticks)
+input-delay-hint = Súbelo si las carreras en línea van a saltos
+keys = Teclas
+keys-hint = Pulsa un botón y luego la tecla que quieras usar. Escape deja las teclas de antes.
+keys-alone = Jugando solo
+key-jump = Saltar
+key-duck = Agacharse
+keys-waiting = Pulsa una tecla…
+attract-mode = Demostración del piloto automático en el menú principal
+field-theme = Tema del campo de juego
+theme-classic = Clásico
+theme-dark = Oscuro
+theme-high-contrast = Alto contraste
+theme-custom = Personalizado
+theme-sprites = Figuras
+theme-background = Fondo
+effects = Efectos
+effects-hint = Partículas y temblor de pantalla. Desactivados para reducir el movimiento
+effects-off = Desactivados
+effects-low = Bajos
+effects-full = Completos
+narrate-obstacles = Anunciar obstáculos
+narrate-obstacles-hint = Los lectores de pantalla dicen qué viene y cuándo
+play-intro = Mostrar la introducción
+play-intro-hint = Intro la salta durante una partida
+clip-length = Duración del clip (segundos)
+clip-length-hint = 0 guarda la partida entera, dure lo que dure
+clip-format = Formato del clip
+clip-format-gif = GIF animado
+clip-format-png-sequence = Secuencia de PNG
+reset-settings = Restablecer valores predeterminados
+
+## Playing
+
+game-heading = Juego del dinosaurio
+play-field = Campo de juego
+lane-label = J{ $player }
+ready = ¿Listo?
+ready-player-one = El jugador 1 salta con w o espacio y se agacha con s.
+ready-player-two = El jugador 2 salta con la flecha arriba y se agacha con la flecha abajo.
+ready-start = Haz clic o pulsa espacio, w o la flecha arriba para empezar.
+ready-skip-intro = Pulsa intro para saltar la introducción.
+finish-run = Terminar partida
+
+## After a run
+
+run-finished = Partida terminada, ¿jugar otra vez?
+times-up = Se acabó el tiempo, ¿jugar otra vez?
+you-died = Has muerto, ¿jugar otra vez?
+assisted-run = Las ayudas estaban activadas, así que esta partida se guarda aparte de tu récord.
+power-ups-run = Los potenciadores estaban activados, así que esta partida no cuenta para tu récord.
+practice-run = Esta era una partida de práctica, solo cuenta la primera del día.
+play-again = Jugar otra vez
+main-menu = Volver al menú principal
+copy-replay = Copiar repetición
+copy-replay-hint = Compruébala con el comando verify del binario headless
+save-clip = Guardar clip
+clip-saving = Guardando el clip…
+clip-saved = Guardado { $files }
+clip-failed = No se pudo guardar el clip: { $error }
+you = Tú
+player = Jugador { $player }
+you-win = ¡Has ganado!
+player-wins = ¡Gana { $player }!
+draw = ¡Empate!
+player-score = { $player }: { $score }
+
+## Screen readers
+
+narration-game-over = Fin de la partida. Puntuación { $score }.
+narration-milestone = { $score } puntos.
+narration-high-score = ¡Nuevo récord!
+narration-obstacle = { $obstacle } en { $seconds } segundos.
+obstacle-small-cactus = Cactus pequeño
+obstacle-wide-cactus = Cactus ancho
+
+## Moving progress
+
+save-data = Llevar tu progreso
+save-data-hint = Lleva tus récords, historial y ajustes a otro dispositivo o navegador.
+copy-save-code = Copiar código de guardado
+save-code-copied = Código de guardado copiado.
+export-save = Exportar a un archivo
+save-exported = Guardado { $file }
+save-export-failed = No se pudo exportar la partida guardada: { $error }
+save-code-hint = Pega un código de guardado o suelta un archivo de guardado en la ventana
+import-save = Importar
+import-save-hint = Sustituye tu progreso aquí
+save-imported = Progreso importado.
+save-import-failed = No se pudo importar la partida guardada: { $error }
+
+## Starting up
+
+startup-save-failed = No se pudo cargar parte del progreso guardado. Se conserva tal cual hasta que lo empieces de nuevo o lo elimines en Perfiles: { $error }
+startup-profiles-failed = No se pudo cargar la lista de perfiles, así que no se guarda nada hasta que empieces de nuevo en Perfiles: { $error }
+startup-asset-map-failed = No se pudo cargar el mapa de recursos, así que se usa el incluido: { $error }
+dismiss = Cerrar
+
+## Profiles
+
+profile = Perfil
+manage-profiles = Perfiles
+profiles = Perfiles
+profiles-hint = Cada persona que comparte este ordenador puede tener sus propios récords, historial y ajustes.
+rename = Renombrar
+delete = Eliminar
+delete-profile-confirm = ¿Eliminar { $name } y todo su progreso?
+profile-name = Nombre
+create-profile = Añadir perfil
+profile-name-empty = Un perfil necesita un nombre.
+profile-name-too-long = Ese nombre es demasiado largo.
+profile-name-taken = Ya hay un perfil con ese nombre.
+profiles-damaged = No se pudo cargar la lista de perfiles, así que no se está guardando nada de esto.
+profile-damaged = Dañado
+start-over = Empezar de nuevo
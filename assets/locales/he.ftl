@@ -0,0 +1,184 @@
+## Main menu
+
+title = משחק הדינוזאור
+one-player = שחקן אחד
+two-players = שני שחקנים
+against-autopilot = נגד הטייס האוטומטי
+best = שיא: { $score }
+best-with-assists = שיא עם עזרים: { $score }
+assists-are-on = העזרים פעילים, ולכן ריצות לא יקבעו שיא.
+play = שחק!
+assists = עזרים
+settings = הגדרות
+credits = קרדיטים
+go-back = חזרה
+recent-runs = ריצות אחרונות
+assisted = עם עזרים
+
+mode-classic = קלאסי
+mode-classic-description = קפוץ מעל הקקטוסים כמה שיותר זמן.
+mode-zen = זן
+mode-zen-description = שום דבר לא יכול לפגוע בך, רק ראה כמה רחוק תגיע.
+mode-time-attack = נגד השעון
+mode-time-attack-description = הגע כמה שיותר רחוק ב־60 שניות.
+mode-daily = אתגר יומי
+mode-daily-description = כולם מקבלים היום את אותו מסלול. רק הריצה הראשונה שלך היום נחשבת.
+
+daily-official-score = הניקוד הרשמי של היום: { $score }. ריצות נוספות היום הן אימון.
+daily-next-run-official = הריצה הבאה שלך היא הניסיון הרשמי של היום.
+daily-needs-standard-rules = כבה את העזרים והחיזוקים כדי לשחק את הניסיון הרשמי של היום.
+daily-leaderboard = טבלת המובילים היומית
+
+race-online = מרוץ ברשת
+relay = שרת ממסר
+room = חדר
+room-empty = בחר חדר שבו תפגוש את היריב.
+online-uses-classic-rules = מרוצים ברשת תמיד משתמשים בחוקים הקלאסיים.
+find-opponent = מצא יריב
+waiting-for-opponent = ממתין ליריב בחדר { $room }
+cancel = ביטול
+
+credits-made-by = נוצר על ידי { $author }
+credits-thanks = תודה רבה למקורות ברשת שאפשרו את התוכנה הזו.
+powered-by = מופעל על ידי
+powered-by-and = ו־
+source-code = קוד מקור.
+file = קובץ
+quit = יציאה
+
+## Assists
+
+assists-kept-apart = ריצות עם עזר כלשהו נשמרות בנפרד מהשיאים שלך.
+one-switch = משחק במתג אחד
+one-switch-hint = כל כפתור קופץ מהקרקע ומוריד בחזרה באוויר
+auto-jump = קפיצה אוטומטית
+auto-jump-hint = המשחק קופץ מעל מכשולים בשבילך. עדיין אפשר לקפוץ, להתכופף ולרדת בעצמך.
+game-speed = מהירות המשחק %
+extra-jump-buffer = חוצץ קפיצה נוסף בטיקים
+extra-jump-buffer-hint = קפיצות שנלחצו מוקדם כל כך עדיין מתבצעות בנחיתה
+assists-off = כבה את כל העזרים
+
+## Settings
+
+language = שפה
+language-system = מערכת: { $language }
+jump-buffer = חוצץ קפיצה בטיקים
+coyote-time = זמן קויוטי בטיקים
+forgiveness-hint = שינוי מברירת המחדל נחשב לעזר
+power-ups = חיזוקים
+power-ups-hint = השאר כבוי למשחק הקלאסי. ריצות עם חיזוקים לא קובעות שיא
+input-delay = השהיית קלט ברשת בטיקים
+input-delay-hint = הגדל אם מרוצים ברשת מגמגמים
+keys = מקשים
+keys-hint = לחץ על כפתור ואז על המקש החדש. Escape משאיר את המקשים הקודמים.
+keys-alone = משחק לבד
+key-jump = קפיצה
+key-duck = התכופפות
+keys-waiting = לחץ על מקש…
+attract-mode = הדגמת טייס אוטומטי בתפריט הראשי
+field-theme = ערכת צבעים למגרש
+theme-classic = קלאסית
+theme-dark = כהה
+theme-high-contrast = ניגודיות גבוהה
+theme-custom = מותאמת אישית
+theme-sprites = דמויות
+theme-background = רקע
+effects = אפקטים
+effects-hint = חלקיקים ורעידת מסך. כבוי מתאים לצמצום תנועה
+effects-off = כבוי
+effects-low = מעט
+effects-full = מלא
+narrate-obstacles = הכרז על מכשולים
+narrate-obstacles-hint = קוראי מסך אומרים מה מתקרב ומתי
+play-intro = הצג את הפתיחה
+play-intro-hint = אנטר מדלג עליה במהלך ריצה
+clip-length = אורך הקליפ בשניות
+clip-length-hint = 0 שומר את כל הריצה, בכל אורך
+clip-format = פורמט הקליפ
+clip-format-gif = GIF מונפש
+clip-format-png-sequence = רצף PNG
+reset-settings = אפס לברירת המחדל
+
+## Playing
+
+game-heading = משחק הדינו
+play-field = מגרש המשחק
+lane-label = ש{ $player }
+ready = מוכן?
+ready-player-one = שחקן 1 קופץ עם w או רווח ומתכופף עם s.
+ready-player-two = שחקן 2 קופץ עם החץ למעלה ומתכופף עם החץ למטה.
+ready-start = לחץ או הקש רווח, w או חץ למעלה כדי להתחיל.
+ready-skip-intro = הקש אנטר כדי לדלג על הפתיחה.
+finish-run = סיים ריצה
+
+## After a run
+
+run-finished = הריצה הסתיימה, לשחק שוב?
+times-up = נגמר הזמן, לשחק שוב?
+you-died = מתת, לשחק שוב?
+assisted-run = העזרים היו פעילים, ולכן הריצה הזו נשמרת בנפרד מהשיא שלך.
+power-ups-run = חיזוקים היו פעילים, ולכן הריצה הזו לא נחשבת לשיא שלך.
+practice-run = זו הייתה ריצת אימון, רק הריצה הראשונה של היום נחשבת.
+play-again = שחק שוב
+main-menu = חזרה לתפריט הראשי
+copy-replay = העתק הקלטה
+copy-replay-hint = בדוק אותה עם פקודת verify של התוכנה headless
+save-clip = שמור קליפ
+clip-saving = שומר את הקליפ…
+clip-saved = נשמר { $files }
+clip-failed = לא ניתן לשמור את הקליפ: { $error }
+you = אתה
+player = שחקן { $player }
+you-win = ניצחת!
+player-wins = { $player } ניצח!
+draw = תיקו!
+player-score = { $player }: { $score }
+
+## Screen readers
+
+narration-game-over = המשחק נגמר. ניקוד { $score }.
+narration-milestone = { $score } נקודות.
+narration-high-score = שיא חדש!
+narration-obstacle = { $obstacle } בעוד { $seconds } שניות.
+obstacle-small-cactus = קקטוס קטן
+obstacle-wide-cactus = קקטוס רחב
+
+## Moving progress
+
+save-data = העברת ההתקדמות שלך
+save-data-hint = קח את השיאים, ההיסטוריה וההגדרות שלך למכשיר או לדפדפן אחר.
+copy-save-code = העתק קוד שמירה
+save-code-copied = קוד השמירה הועתק.
+export-save = ייצא לקובץ
+save-exported = נשמר { $file }
+save-export-failed = לא ניתן לייצא את השמירה: { $error }
+save-code-hint = הדבק קוד שמירה, או גרור קובץ שמירה לחלון
+import-save = ייבא
+import-save-hint = מחליף את ההתקדמות שלך כאן
+save-imported = ההתקדמות יובאה.
+save-import-failed = לא ניתן לייבא את השמירה: { $error }
+
+## Starting up
+
+startup-save-failed = לא ניתן לטעון חלק מההתקדמות השמורה. היא נשמרת כמו שהיא עד שתתחיל אותה מחדש או תמחק אותה בפרופילים: { $error }
+startup-profiles-failed = לא ניתן לטעון את רשימת הפרופילים, ולכן שום דבר לא נשמר עד שתתחיל מחדש בפרופילים: { $error }
+startup-asset-map-failed = לא ניתן לטעון את מפת הנכסים, ולכן נעשה שימוש במפה המובנית: { $error }
+dismiss = סגור
+
+## Profiles
+
+profile = פרופיל
+manage-profiles = פרופילים
+profiles = פרופילים
+profiles-hint = כל מי שחולק את המחשב הזה יכול לקבל שיאים, היסטוריה והגדרות משלו.
+rename = שנה שם
+delete = מחק
+delete-profile-confirm = למחוק את { $name } ואת כל ההתקדמות?
+profile-name = שם
+create-profile = הוסף פרופיל
+profile-name-empty = לפרופיל צריך להיות שם.
+profile-name-too-long = השם הזה ארוך מדי.
+profile-name-taken = כבר יש פרופיל בשם הזה.
+profiles-damaged = לא ניתן לטעון את רשימת הפרופילים, ולכן שום דבר כאן לא נשמר.
+profile-damaged = פגום
+start-over = התחל מחדש
@@ -0,0 +1,138 @@
+//! # animation
+//! Sprites that change over time.
+//!
+//! Each [`Clip`] is a list of frames and how many ticks each is shown for, so changing how an
+//! animation looks means changing the tables below rather than the code. Every animated thing
+//! keeps an [`Animator`] that tracks where it is in its clip.
+
+use crate::sprite::{self, Sprite};
+
+/// One picture in a clip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    pub sprite: Sprite,
+
+    /// How many ticks the frame is shown for at normal speed.
+    pub ticks: u32,
+}
+
+/// A named sequence of frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clip {
+    pub name: &'static str,
+    pub frames: &'static [Frame],
+
+    /// Whether the clip starts again after its last frame, rather than holding it.
+    pub looping: bool,
+}
+
+const fn frame(sprite: Sprite, ticks: u32) -> Frame {
+    Frame { sprite, ticks }
+}
+
+pub const RUN: Clip = Clip {
+    name: "run",
+    frames: &[frame(sprite::DINO_RIGHT, 7), frame(sprite::DINO_LEFT, 7)],
+    looping: true,
+};
+
+pub const DUCK_RUN: Clip = Clip {
+    name: "duck-run",
+    frames: &[
+        frame(sprite::DINO_DUCK_LEFT, 7),
+        frame(sprite::DINO_DUCK_RIGHT, 7),
+    ],
+    looping: true,
+};
+
+pub const JUMP: Clip = Clip {
+    name: "jump",
+    frames: &[frame(sprite::DINO_STILL, 1)],
+    looping: false,
+};
+
+pub const DEAD: Clip = Clip {
+    name: "dead",
+    frames: &[frame(sprite::DINO_DEAD, 1)],
+    looping: false,
+};
+
+pub const BIRD_FLAP: Clip = Clip {
+    name: "bird-flap",
+    frames: &[frame(sprite::BIRD_UP, 10), frame(sprite::BIRD_DOWN, 10)],
+    looping: true,
+};
+
+pub const BLINK_IDLE: Clip = Clip {
+    name: "blink-idle",
+    frames: &[
+        frame(sprite::DINO_STILL, 150),
+        frame(sprite::DINO_BLINK, 8),
+        frame(sprite::DINO_STILL, 40),
+        frame(sprite::DINO_BLINK, 8),
+    ],
+    looping: true,
+};
+
+pub const CLIPS: [&Clip; 6] = [&RUN, &DUCK_RUN, &JUMP, &DEAD, &BIRD_FLAP, &BLINK_IDLE];
+
+/// Looks up a clip by its name.
+pub fn clip(name: &str) -> Option<&'static Clip> {
+    CLIPS.into_iter().find(|clip| clip.name == name)
+}
+
+/// Where something is in its current clip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Animator {
+    clip: &'static Clip,
+    frame: usize,
+
+    /// Ticks the current frame has been shown for, which needn't be whole at other speeds.
+    elapsed: f64,
+}
+
+impl Animator {
+    pub fn new(clip: &'static Clip) -> Self {
+        Self {
+            clip,
+            frame: 0,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn clip(&self) -> &'static Clip {
+        self.clip
+    }
+
+    /// Switches to `clip` from its first frame, unless it is already playing.
+    pub fn play(&mut self, clip: &'static Clip) {
+        if self.clip != clip {
+            *self = Self::new(clip);
+        }
+    }
+
+    /// Moves the clip on by one tick played `speed` times as fast as normal.
+    pub fn advance(&mut self, speed: f64) {
+        self.elapsed += speed;
+        loop {
+            let ticks = self.clip.frames[self.frame].ticks as f64;
+            if self.elapsed < ticks {
+                break;
+            }
+            if self.frame + 1 < self.clip.frames.len() {
+                self.frame += 1;
+            } else if self.clip.looping {
+                self.frame = 0;
+            } else {
+                self.elapsed = ticks;
+                break;
+            }
+            self.elapsed -= ticks;
+        }
+    }
+
+    /// The sprite to draw now.
+    pub fn sprite(&self) -> Sprite {
+        self.clip.frames[self.frame].sprite
+    }
+}
@@ -5,54 +5,146 @@
 //! There will be a main menu, a screen befor the user starts the game, a screen for when the user
 //! dies and a screen for after the player dies.
 
-use crate::render;
+use crate::assets::{self, Atlas};
+use crate::assist::Assisted;
+use crate::clip;
+use crate::controller::{Autopilot, Controller, Keyboard, View};
+use crate::death::DeathSequence;
+use crate::input::Input;
+use crate::locale::{self, Language, Locale};
+use crate::mode::{self, DailyRecord, GameMode, RunHistory, RunResult, TIME_ATTACK_TICKS};
+use crate::narration::Narrator;
+use crate::net::{self, Event, Session};
+use crate::powerup::BADGE_TOP;
+use crate::profile::{Profiles, MAX_NAME_LENGTH};
+use crate::raster::Raster;
+use crate::render::{self, Renderer};
+use crate::replay::Replay;
+use crate::run::Run;
+use crate::save::{self, SaveData, SAVE_VERSION};
+use crate::settings::Settings;
+use crate::sprite;
 use anyhow::Result;
 use eframe::egui;
-use egui::{Key, Painter, Pos2, Sense, Ui};
+use egui::{accesskit, Align2, Color32, FontId, Key, Painter, Pos2, Response, Sense, Ui, Vec2};
 use egui_demo_lib::easy_mark;
+use image::RgbaImage;
 use log::warn;
 use rand::prelude::*;
+use std::collections::HashMap;
 use std::path::Path;
 
-pub fn load_image_from_path(path: &std::path::Path) -> Result<egui::ColorImage> {
-    let image = image::ImageReader::open(path)?.decode()?;
-    let size = [image.width() as _, image.height() as _];
-    let image_buffer = image.to_rgba8();
-    let pixels = image_buffer.as_flat_samples();
-    Ok(egui::ColorImage::from_rgba_unmultiplied(
-        size,
-        pixels.as_slice(),
-    ))
-}
+/// The `dino_y` of a dino standing on the floor.
+pub(crate) const GROUND_Y: f64 = 100.0;
+
+/// The dino only clears obstacles while `dino_y` is above this.
+pub(crate) const CLEAR_Y: f64 = 54.0;
+
+/// Added to `dino_speed_y` every tick the dino is in the air.
+pub(crate) const GRAVITY: f64 = 1.2;
+
+/// The `dino_speed_y` given by a jump.
+pub(crate) const JUMP_SPEED: f64 = -20.0;
+
+/// Letting go of jump only cuts the jump short once the dino has risen above this.
+pub(crate) const MIN_JUMP_Y: f64 = 70.0;
+
+/// Holding jump stops making the dino rise once it reaches this.
+pub(crate) const MAX_JUMP_Y: f64 = -70.0;
+
+/// The fastest the dino keeps rising once a jump has been cut short.
+pub(crate) const DROP_SPEED: f64 = -10.0;
+
+/// Added to `dino_speed_y` every tick the dino is fast-falling.
+pub(crate) const FAST_FALL_GRAVITY: f64 = GRAVITY * 3.0;
+
+/// Converts `dino_speed` into distance moved per tick.
+pub(crate) const SPEED_SCALE: f64 = 0.3;
+
+/// How much `dino_speed` increases each tick.
+pub(crate) const SPEED_GAIN: f64 = 0.026;
+
+/// Obstacles hit the dino while their `start_x` is below this and their `end_x` above
+/// `HITBOX_BACK`.
+pub(crate) const HITBOX_FRONT: f64 = 105.0;
+pub(crate) const HITBOX_BACK: f64 = -15.0;
+
+/// The dino only reaches power-ups while `dino_y` is greater than this. `y` grows down the
+/// screen, so that is while its feet are low enough to touch the badge: the dino is drawn 150
+/// further down than its `dino_y` and is 90 tall.
+pub(crate) const REACH_Y: f64 = BADGE_TOP - 240.0;
+
+/// How far apart the lanes of a two player race are drawn.
+pub(crate) const LANE_HEIGHT: f64 = 300.0;
+
+/// Where the top of a lane is drawn, relative to the lane's y offset. This is as high as the
+/// dino can jump.
+const LANE_TOP: f64 = 70.0;
 
-#[derive(PartialEq)]
+/// How much of the play field shows before the intro wipes the rest into view, in points.
+const INTRO_WIDTH: f32 = 200.0;
+
+/// Opponents in online races are drawn see-through.
+const GHOST_TINT: Color32 = Color32::from_rgba_premultiplied(90, 90, 90, 90);
+
+#[derive(Clone, PartialEq)]
 enum AppStatus {
     Menu,
     Credits,
-    //Settings,
+    Settings,
+    Assists,
+    Profiles,
+    WaitingForOpponent,
     GameReadyToStart,
     PlayingGame,
     Died,
 }
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Enemy {
     pub start_x: f64,
-    end_x: f64,
+    pub end_x: f64,
     image: usize,
 
     pub height: f64,
     can_duck: bool,
-    ignore: bool,
+    pub(crate) ignore: bool,
 }
 
 impl Default for Enemy {
     fn default() -> Self {
-        let mut rng = rand::rng();
+        Self::random(&mut rand::rng())
+    }
+}
+
+impl Enemy {
+    /// Whether the dino can get under the obstacle by ducking.
+    pub fn can_duck(&self) -> bool {
+        self.can_duck
+    }
+
+    /// How far above the floor the dino has to be to clear the obstacle. Every obstacle has the
+    /// same hitbox for now, whatever it looks like.
+    pub fn hitbox_height(&self) -> f64 {
+        GROUND_Y - CLEAR_Y
+    }
+
+    /// The id of what the obstacle is in the [`Locale`](crate::locale::Locale), for screen
+    /// readers.
+    pub fn message_id(&self) -> &'static str {
+        if self.image == 1 {
+            "obstacle-small-cactus"
+        } else {
+            "obstacle-wide-cactus"
+        }
+    }
+
+    /// Creates an enemy of a random type at the right edge of the play area.
+    pub fn random(rng: &mut impl Rng) -> Self {
         let e_type = rng.random_range(1..=2) as f64;
         Self {
-            start_x: 1800.0,
-            end_x: 1730.0 + 10.0 * e_type,
+            start_x: crate::spawner::SPAWN_X,
+            end_x: crate::spawner::SPAWN_X - 70.0 + 10.0 * e_type,
             image: e_type as usize,
             height: e_type,
             can_duck: false,
@@ -61,60 +153,150 @@ impl Default for Enemy {
     }
 }
 
-/// We derive Deserialize/Serialize so we can persist app state on shutdown.
-#[derive(serde::Deserialize, serde::Serialize)]
-#[serde(default)] // if we add new fields, give them default values when deserializing old state
+/// The whole app. What is worth keeping when it closes goes in a [`SaveData`].
+#[derive(Clone)]
 pub struct DinoGame {
-    // Example stuff:
-    label: String,
+    /// The buttons held during the previous frame on the screens around the game.
+    held: Input,
 
-    #[serde(skip)]
-    dino_speed_y: f64,
+    /// The best score in the current mode.
+    high_score: f64,
 
-    #[serde(skip)]
-    dino_y: f64,
+    mode: GameMode,
 
-    #[serde(skip)]
-    dino_distance: f64,
-    high_score: f64,
+    /// The best score in each mode other than the current one.
+    best_scores: HashMap<GameMode, f64>,
+
+    daily: DailyRecord,
+
+    /// The best score in each mode of runs played with assists, kept apart from the others.
+    assisted_scores: HashMap<GameMode, f64>,
+
+    /// The last few one player runs.
+    history: RunHistory,
+
+    /// The day the current run started on, as a daily challenge run counts for that day.
+    run_day: i64,
+
+    /// Whether the current run can set a best score. Only runs with the standard rules are
+    /// official, and only the first daily challenge run of a day.
+    official: bool,
+
+    /// Whether the next run is a race between two players on the same keyboard.
+    two_player: bool,
+
+    /// Whether the second player in a two player race is the autopilot.
+    bot_opponent: bool,
+
+    /// Who presses the buttons for each run.
+    controllers: Vec<Box<dyn Controller>>,
+
+    /// The run the autopilot plays under the main menu.
+    attract: Option<Run>,
+
+    /// One run per player, all over the same course.
+    runs: Vec<Run>,
+
+    /// The buttons held so far in a one player run, so it can be played back.
+    replay: Option<Replay>,
+
+    /// Where the last clip was saved, or why it couldn't be.
+    clip_status: Option<String>,
+
+    /// The clip being saved, if one is.
+    saving_clip: Option<clip::Saving>,
+
+    /// The crash animation, once every run is over.
+    death: Option<DeathSequence>,
+
+    /// Watches the run for things a screen reader should announce.
+    narrator: Narrator,
+
+    /// What the screen reader was last told about the run.
+    announcement: String,
+
+    /// The relay online races are played through.
+    relay: String,
+
+    /// The room to meet opponents in on the relay.
+    room: String,
+
+    online: Option<Session>,
+
+    /// Why the last online race ended early, shown on the main menu.
+    online_error: Option<String>,
+
+    /// A save code being pasted in to import.
+    save_code: String,
+
+    /// Whether the last import or export worked.
+    save_status: Option<String>,
+
+    /// Everyone who plays on this machine. The progress of the profile being played is kept in
+    /// the game itself, and only copied back into here when switching profiles or saving.
+    profiles: Profiles,
+
+    /// A name being typed for a new or renamed profile.
+    profile_name: String,
 
-    #[serde(skip)]
-    dino_speed: f64,
+    /// The profile being renamed, or `None` when the name is for a new profile.
+    renaming: Option<usize>,
 
-    #[serde(skip)]
-    enemys: Vec<Enemy>,
+    /// The profile waiting for its deletion to be confirmed.
+    deleting: Option<usize>,
+
+    /// What went wrong while starting up, as a message id and the error, shown on the main menu
+    /// until dismissed.
+    startup_problems: Vec<(&'static str, String)>,
 
-    #[serde(skip)]
     state: AppStatus,
 
-    #[serde(skip)] // This how you opt-out of serialization of a field
-    tick: i32,
-    #[serde(skip)]
-    cooldown: i32,
+    pub settings: Settings,
 
-    #[serde(skip)]
-    intro_mode: i32,
+    /// The menus' text in the language from the settings.
+    locale: Locale,
 
-    #[serde(skip)]
-    pub asset_map: Option<egui::TextureHandle>,
+    renderer: Renderer,
 }
 
 impl Default for DinoGame {
     fn default() -> Self {
         Self {
-            // Example stuff:
-            label: "Hello World!".to_owned(),
-            tick: 0,
             state: AppStatus::Menu,
-            dino_y: 100.0,
-            dino_speed_y: 0.0,
-            dino_speed: 25.0,
-            dino_distance: 0.0,
+            held: Input::default(),
             high_score: 0.0,
-            cooldown: 20,
-            intro_mode: 0,
-            enemys: Vec::new(),
-            asset_map: None,
+            mode: GameMode::default(),
+            best_scores: HashMap::new(),
+            daily: DailyRecord::default(),
+            assisted_scores: HashMap::new(),
+            history: RunHistory::default(),
+            run_day: 0,
+            official: true,
+            two_player: false,
+            bot_opponent: false,
+            controllers: keyboards(&Settings::default(), 1),
+            attract: None,
+            runs: vec![Run::default()],
+            replay: None,
+            clip_status: None,
+            saving_clip: None,
+            death: None,
+            narrator: Narrator::default(),
+            announcement: String::new(),
+            relay: net::DEFAULT_RELAY.to_owned(),
+            room: "dino".to_owned(),
+            online: None,
+            online_error: None,
+            save_code: String::new(),
+            save_status: None,
+            profiles: Profiles::default(),
+            profile_name: String::new(),
+            renaming: None,
+            deleting: None,
+            startup_problems: Vec::new(),
+            settings: Settings::default(),
+            locale: Locale::default(),
+            renderer: Renderer::default(),
         }
     }
 }
@@ -122,44 +304,458 @@ impl Default for DinoGame {
 impl DinoGame {
     /// Called once before the first frame.
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+        locale::install_fonts(&cc.egui_ctx);
+        // there are no files to look in on the web
+        let asset_map = cfg!(not(target_arch = "wasm32")).then(|| Path::new(assets::OVERRIDE_PATH));
+        Self::restore(cc.storage, asset_map)
+    }
+
+    /// Starts the game with the progress saved in `storage` and the asset map at `asset_map`, if
+    /// there are any. Anything that can't be loaded is left as it is in a new game and reported
+    /// on the main menu, so the game always starts. Saves that can't be loaded are kept in
+    /// storage as they are, see [`Profiles`].
+    pub fn restore(storage: Option<&dyn eframe::Storage>, asset_map: Option<&Path>) -> Self {
+        let locale = Locale::new(Language::detect());
+        let first = locale.speak_with("player", &[("player", 1.into())]);
+        let mut game = Self {
+            profiles: Profiles::new(first.clone(), SaveData::default()),
+            locale,
+            ..Default::default()
+        };
+        if let Some(storage) = storage {
+            let profiles = Profiles::load(storage, first);
+            if let Some(err) = profiles.damaged() {
+                game.report("startup-profiles-failed", err);
+            }
+            for profile in profiles.all() {
+                if let Some(err) = profile.damaged() {
+                    game.report("startup-save-failed", err);
+                }
+            }
+            game.load_save(profiles.current().save.clone());
+            game.profiles = profiles;
+        }
+        if let Some(path) = asset_map {
+            match assets::load_override(path) {
+                Ok(Some(atlas)) => game.renderer.set_atlas(Atlas::new(atlas)),
+                Ok(None) => {}
+                Err(err) => game.report("startup-asset-map-failed", err),
+            }
+        }
+        game
+    }
+
+    /// Logs a problem from starting up and keeps it for the main menu.
+    fn report(&mut self, id: &'static str, err: impl std::fmt::Display) {
+        warn!("{err:#}");
+        self.startup_problems.push((id, format!("{err:#}")));
+    }
+
+    /// What went wrong while starting up, as message ids for the [`Locale`].
+    pub fn startup_problems(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.startup_problems.iter().map(|(id, _)| *id)
+    }
+
+    /// Creates a game that is already running, with its obstacles determined by `seed`.
+    ///
+    /// This needs no egui context, so the game can be driven with [`Self::step`] without a
+    /// window.
+    pub fn simulation(seed: u64) -> Self {
+        Self::race(seed, 1)
+    }
+
+    /// Like [`Self::simulation`], with `players` dinos racing over the same course.
+    pub fn race(seed: u64, players: usize) -> Self {
+        let mut game = Self {
+            state: AppStatus::PlayingGame,
+            runs: (0..players).map(|_| Run::new(seed)).collect(),
+            controllers: keyboards(&Settings::default(), players),
+            replay: (players == 1).then(|| {
+                Replay::new(
+                    seed,
+                    GameMode::Classic,
+                    Settings::default(),
+                    Input::default(),
+                )
+            }),
+            ..Default::default()
+        };
+        for run in &mut game.runs {
+            let _ = run.jump();
         }
-        let mut temp: Self = Self {
+        game
+    }
+
+    /// Sets up a new run in the current mode, keeping everything that outlives a single run.
+    fn new_run(&mut self) {
+        let run_day = mode::today();
+        let players = if self.two_player { 2 } else { 1 };
+        let seed = self.mode.seed();
+        *self = Self {
+            high_score: self.high_score,
+            mode: self.mode,
+            best_scores: std::mem::take(&mut self.best_scores),
+            daily: std::mem::take(&mut self.daily),
+            assisted_scores: std::mem::take(&mut self.assisted_scores),
+            history: std::mem::take(&mut self.history),
+            run_day,
+            official: !self.two_player
+                && self.settings.standard_rules()
+                && (self.mode != GameMode::Daily || self.daily.result(run_day).is_none()),
+            two_player: self.two_player,
+            bot_opponent: self.bot_opponent,
+            controllers: self.controllers_for(players),
+            runs: (0..players).map(|_| self.new_lane(seed)).collect(),
+            replay: (players == 1)
+                .then(|| Replay::new(seed, self.mode, self.settings.clone(), Input::default())),
+            relay: std::mem::take(&mut self.relay),
+            room: std::mem::take(&mut self.room),
+            online: self.online.take(),
+            held: self.held,
+            settings: self.settings.clone(),
+            profiles: std::mem::take(&mut self.profiles),
+            locale: std::mem::take(&mut self.locale),
+            renderer: std::mem::take(&mut self.renderer),
+            state: AppStatus::GameReadyToStart,
             ..Default::default()
         };
-        temp.asset_map = Some(cc.egui_ctx.load_texture(
-            "asset_map",
-            load_image_from_path(Path::new("asset-map.png")).unwrap(),
-            egui::TextureOptions::default(),
+    }
+
+    /// A run for one lane of a new race, already past the intro if it is turned off.
+    fn new_lane(&self, seed: u64) -> Run {
+        let mut run = Run::new(seed);
+        if !self.settings.play_intro {
+            run.skip_intro();
+        }
+        run
+    }
+
+    /// The controllers for a new run with `players` players.
+    fn controllers_for(&self, players: usize) -> Vec<Box<dyn Controller>> {
+        let mut controllers = keyboards(&self.settings, players);
+        if players > 1 && self.bot_opponent {
+            controllers[0] = Box::new(Keyboard::new(self.settings.keys.clone()));
+            controllers[1] = Box::new(Autopilot::default());
+        }
+        let assists = &self.settings.assists;
+        if assists.changes_buttons() {
+            // assists help the people playing, not the autopilot
+            let people = controllers
+                .iter_mut()
+                .filter(|controller| controller.is_human());
+            for controller in people {
+                *controller = Box::new(Assisted::new(controller.clone(), assists));
+            }
+        }
+        controllers
+    }
+
+    /// Hands control of `player`'s run to `controller`, which can be a bot.
+    pub fn set_controller(&mut self, player: usize, controller: impl Controller + 'static) {
+        if let Some(slot) = self.controllers.get_mut(player) {
+            *slot = Box::new(controller);
+        }
+    }
+
+    /// Starts a new run straight away.
+    fn restart(&mut self) {
+        self.new_run();
+        self.start();
+    }
+
+    /// Starts the runs, with every dino jumping.
+    fn start(&mut self) {
+        self.state = AppStatus::PlayingGame;
+        // only a run that can set a best score can beat it
+        let best = if self.official {
+            self.high_score
+        } else {
+            f64::INFINITY
+        };
+        self.narrator = Narrator::new(best, self.settings.narrate_obstacles);
+        if self.mode == GameMode::Daily && self.official {
+            self.daily.start(self.run_day);
+        }
+        if let Some(replay) = &mut self.replay {
+            replay.held = self.held;
+        }
+        for run in &mut self.runs {
+            run.held = self.held;
+            let _ = run.jump();
+        }
+    }
+
+    /// Connects to the relay and waits for an opponent.
+    fn join_online(&mut self) {
+        self.online = Some(Session::connect(
+            &self.relay,
+            self.room.trim(),
+            self.settings.input_delay,
         ));
+        self.online_error = None;
+        self.state = AppStatus::WaitingForOpponent;
+    }
 
-        temp
+    /// Starts an online race once the relay has found an opponent.
+    fn start_online(&mut self, seed: u64, players: usize) {
+        self.new_run();
+        self.runs = (0..players).map(|_| self.new_lane(seed)).collect();
+        self.controllers = vec![Box::new(Keyboard::new(self.settings.keys.clone())); players];
+        self.replay = None;
+        self.official = false;
+        self.start();
+    }
+
+    /// Disconnects from the relay and returns to the main menu, showing `error` if there is one.
+    fn leave_online(&mut self, error: Option<String>) {
+        if let Some(session) = self.online.take() {
+            session.close();
+        }
+        self.online_error = error;
+        self.state = AppStatus::Menu;
+    }
+
+    /// The settings and mode the runs are simulated with. Every client in an online race has to
+    /// simulate the same rules, so online races use the classic game.
+    fn rules(&self) -> (Settings, GameMode) {
+        if self.online.is_some() {
+            (Settings::default(), GameMode::Classic)
+        } else {
+            (self.settings.clone(), self.mode)
+        }
+    }
+
+    /// Ends the current run, recording its score.
+    fn end_run(&mut self) {
+        self.state = AppStatus::Died;
+        self.death = Some(DeathSequence::default());
+        if let Some(replay) = &mut self.replay {
+            replay.score = self.runs[0].score;
+        }
+        if self.mode == GameMode::Daily && self.official {
+            self.daily.finish(self.run_day, self.runs[0].score);
+        }
+        if self.runs.len() == 1 && self.online.is_none() {
+            let (score, assisted) = (self.runs[0].score, self.settings.assisted());
+            self.history.record(RunResult {
+                mode: self.mode,
+                score,
+                assisted,
+            });
+            if assisted {
+                let best = self.assisted_scores.entry(self.mode).or_default();
+                *best = best.max(score);
+            }
+        }
+    }
+
+    /// Switches to another mode, swapping in that mode's best score.
+    fn set_mode(&mut self, mode: GameMode) {
+        self.best_scores.insert(self.mode, self.high_score);
+        self.mode = mode;
+        self.high_score = self.best_scores.get(&mode).copied().unwrap_or(0.0);
+    }
+
+    /// Whether the crash animation has gone on long enough for jumping to restart the game.
+    fn accepts_restart(&self) -> bool {
+        self.death
+            .as_ref()
+            .map_or(true, DeathSequence::accepts_restart)
+    }
+
+    /// Whether the dino has hit an obstacle.
+    pub fn is_dead(&self) -> bool {
+        self.state == AppStatus::Died
+    }
+
+    /// Whether the first player's dino is standing on the floor, and so able to jump.
+    pub fn on_ground(&self) -> bool {
+        self.runs[0].on_ground()
+    }
+
+    /// Whether the first player's dino is moving upwards.
+    pub fn rising(&self) -> bool {
+        self.runs[0].rising()
+    }
+
+    /// Whether the first player's dino is high enough to pass over obstacles.
+    pub fn clears_obstacles(&self) -> bool {
+        self.runs[0].clears_obstacles()
+    }
+
+    /// Draws every player's run on the CPU, with `scale` pixels for each game unit.
+    pub fn screenshot(&self, scale: f32) -> RgbaImage {
+        let mut raster = Raster::with_lanes(scale, self.runs.len());
+        raster.set_atlas(self.renderer.atlas().clone());
+        raster.set_theme(self.settings.theme());
+        for (player, run) in self.runs.iter().enumerate() {
+            let y = player as f64 * LANE_HEIGHT;
+            render::draw_run(&mut raster, run, y, &sprite::dino(run), Color32::WHITE);
+        }
+        if let Some(death) = &self.death {
+            let tint = Color32::WHITE.gamma_multiply(death.panel());
+            render::draw_game_over(&mut raster, 0.0, tint);
+        }
+        raster.into_image()
+    }
+
+    /// Saves a screenshot next to the game, named after the time it was taken.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_screenshot(&self) {
+        let time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let path = format!("dino-{time}.png");
+        match self.screenshot(1.0).save(&path) {
+            Ok(()) => log::info!("saved a screenshot to {path}"),
+            Err(err) => warn!("couldn't save a screenshot: {err}"),
+        }
+    }
+
+    /// The replay of a one player run, finished once the run is over.
+    pub fn replay(&self) -> Option<&Replay> {
+        self.replay.as_ref()
+    }
+
+    /// Every player's run, the first player's first.
+    pub fn runs(&self) -> &[Run] {
+        &self.runs
     }
 
     /// Displays the main menu
     fn update_menu(
         &mut self,
-        _ctx: &eframe::egui::Context,
+        ctx: &eframe::egui::Context,
         _frame: &mut eframe::Frame,
         ui: &mut egui::Ui,
     ) {
+        let locale = self.locale.clone();
         ui.vertical_centered(|ui| {
             // The central panel the region left after adding TopPanel's and SidePanel's
-            ui.heading("Dinosaur game");
+            ui.heading(locale.tr("title"));
+            for (id, error) in &self.startup_problems {
+                let message = locale.tr_with(id, &[("error", error.clone().into())]);
+                ui.colored_label(ui.visuals().error_fg_color, message);
+            }
+            if !self.startup_problems.is_empty() && ui.button(locale.tr("dismiss")).clicked() {
+                self.startup_problems.clear();
+            }
+            locale.row(ui, |ui| {
+                let mut picked = self.profiles.current_index();
+                egui::ComboBox::from_label(locale.tr("profile"))
+                    .selected_text(&self.profiles.current().name)
+                    .show_ui(ui, |ui| {
+                        for (index, profile) in self.profiles.all().iter().enumerate() {
+                            if profile.damaged().is_none() {
+                                ui.selectable_value(&mut picked, index, &profile.name);
+                            }
+                        }
+                    });
+                if picked != self.profiles.current_index() {
+                    self.select_profile(picked);
+                }
+                if ui.button(locale.tr("manage-profiles")).clicked() {
+                    self.state = AppStatus::Profiles;
+                }
+            });
 
-            let play_button = ui.button("Play!");
+            locale.row(ui, |ui| {
+                for mode in GameMode::ALL {
+                    if ui
+                        .selectable_label(self.mode == mode, locale.tr(mode.message_id()))
+                        .clicked()
+                    {
+                        self.set_mode(mode);
+                    }
+                }
+            });
+            ui.label(locale.tr(self.mode.description_id()));
+            locale.row(ui, |ui| {
+                ui.selectable_value(&mut self.two_player, false, locale.tr("one-player"));
+                ui.selectable_value(&mut self.two_player, true, locale.tr("two-players"));
+                if self.two_player {
+                    ui.checkbox(&mut self.bot_opponent, locale.tr("against-autopilot"));
+                }
+            });
+            let score = (self.high_score / 85.0) as i32;
+            ui.label(locale.tr_with("best", &[("score", score.into())]));
+            if let Some(best) = self.assisted_scores.get(&self.mode) {
+                let score = (best / 85.0) as i32;
+                ui.label(locale.tr_with("best-with-assists", &[("score", score.into())]));
+            }
+            if self.settings.assisted() {
+                ui.label(locale.tr("assists-are-on"));
+            }
+            if self.mode == GameMode::Daily {
+                self.show_daily(ui);
+            }
+            self.show_online(ui);
+
+            let play_button = ui.button(locale.tr("play"));
 
             if play_button.clicked() {
-                self.state = AppStatus::GameReadyToStart;
+                self.new_run();
+            };
+
+            self.show_history(ui);
+
+            if ui.button(locale.tr("assists")).clicked() {
+                self.state = AppStatus::Assists;
+            }
+
+            let settings_button = ui.button(locale.tr("settings"));
+
+            if settings_button.clicked() {
+                self.state = AppStatus::Settings;
             };
 
-            let credits_button = ui.button("Credits");
+            let credits_button = ui.button(locale.tr("credits"));
 
             if credits_button.clicked() {
                 self.state = AppStatus::Credits;
             };
         });
+
+        if self.settings.attract_mode {
+            if let Err(err) = self.update_attract(ctx, ui) {
+                // the attract run starts over next frame
+                warn!("the game under the menu stopped: {err:#}");
+            }
+        }
+    }
+
+    /// Plays the game with the autopilot under the main menu, starting over whenever it dies.
+    fn update_attract(&mut self, ctx: &eframe::egui::Context, ui: &mut Ui) -> Result<()> {
+        let mut run = match self.attract.take() {
+            Some(run) if !run.is_finished() => run,
+            _ => {
+                let mut run = Run::default();
+                run.skip_intro();
+                run.jump()?;
+                run
+            }
+        };
+        let input = Autopilot::default().act(&View {
+            run: &run,
+            ui: None,
+        });
+        run.step(input, &Settings::default(), GameMode::Classic)?;
+
+        let (response, painter) = ui.allocate_painter(
+            egui::vec2(1300.0, 300.0 - (LANE_TOP as f32) * render::SIZE),
+            Sense::hover(),
+        );
+        let y = response.rect.top() as f64 / render::SIZE as f64 - LANE_TOP;
+        let theme = self.settings.theme();
+        painter.rect_filled(response.rect, 0.0, theme.background);
+        self.renderer.set_theme(theme);
+        self.draw(&painter, |game, renderer| {
+            game.draw_lane(renderer, &run, y, None, &painter)
+        })?;
+        self.attract = Some(run);
+        ctx.request_repaint();
+        Ok(())
     }
 
     /// Displays the credits
@@ -169,357 +765,887 @@ impl DinoGame {
         _frame: &mut eframe::Frame,
         ui: &mut Ui,
     ) {
+        let locale = &self.locale;
         ui.vertical_centered(|ui| {
-            ui.heading("Credits");
-            easy_mark::easy_mark(ui, "# Made by voidapex11\n\nMany thanks to the online resources that made this program possible."); 
+            ui.heading(locale.tr("credits"));
+            let made_by = locale.tr_with("credits-made-by", &[("author", "voidapex11".into())]);
+            easy_mark::easy_mark(
+                ui,
+                &format!("# {made_by}\n\n{}", locale.tr("credits-thanks")),
+            );
             // using markup as well as
             // programaticaly constructing is
             // because why not
             ui.heading("\n\n");
-            if ui.button("Go back").clicked() {
+            if ui.button(locale.tr("go-back")).clicked() {
                 self.state = AppStatus::Menu;
             }
         });
     }
 
-    fn jump(&mut self) -> Result<()> {
-        if self.dino_y != 100.0 {
-            return Ok(());
-        };
-        self.dino_speed_y -= 20.0;
-        Ok(())
+    /// Displays the last few runs, marking the ones played with assists
+    fn show_history(&self, ui: &mut Ui) {
+        if self.history.runs.is_empty() {
+            return;
+        }
+        let locale = &self.locale;
+        ui.collapsing(locale.tr("recent-runs"), |ui| {
+            egui::Grid::new("history").show(ui, |ui| {
+                for run in self.history.runs.iter().rev() {
+                    ui.label(locale.tr(run.mode.message_id()));
+                    ui.label(((run.score / 85.0) as i32).to_string());
+                    ui.label(if run.assisted {
+                        locale.tr("assisted")
+                    } else {
+                        String::new()
+                    });
+                    ui.end_row();
+                }
+            });
+        });
     }
 
-    fn draw_dino_rest(
-        &mut self,
-        x: f64,
-        y: f64,
-        painter: Painter,
-        ui: &mut Ui,
-        ctx: &eframe::egui::Context,
-    ) -> Result<()> {
-        render::draw_dino_rest_state(
-            self,
-            x * (render::SIZE as f64),
-            y * (render::SIZE as f64),
-            painter,
-            ui,
-            ctx,
-        )?;
-        Ok(())
+    /// Displays the assist options
+    fn update_assists(&mut self, ui: &mut Ui) {
+        let locale = &self.locale;
+        ui.vertical_centered(|ui| {
+            ui.heading(locale.tr("assists"));
+            ui.label(locale.tr("assists-kept-apart"));
+            self.settings.assists.show(ui, locale);
+            ui.heading("\n\n");
+            if ui.button(locale.tr("go-back")).clicked() {
+                self.state = AppStatus::Menu;
+            }
+        });
     }
 
-    fn draw_dino(
-        &mut self,
-        mut x: f64,
-        mut y: f64,
-        painter: &Painter,
-        ui: &mut Ui,
-        ctx: &eframe::egui::Context,
-    ) -> Result<()> {
-        x *= render::SIZE as f64;
-        y *= render::SIZE as f64;
-        if self.dino_y != 100.0 || self.state == AppStatus::Died {
-            render::draw_dino_still(self, x, y, painter.clone(), ui, ctx)?;
-            return Ok(());
-        }
+    /// Displays the screen for adding, renaming and deleting profiles
+    fn update_profiles(&mut self, ui: &mut Ui) {
+        let locale = self.locale.clone();
+        ui.vertical_centered(|ui| {
+            ui.heading(locale.tr("profiles"));
+            ui.label(locale.tr("profiles-hint"));
+            if self.profiles.damaged().is_some() {
+                locale.row(ui, |ui| {
+                    ui.colored_label(ui.visuals().error_fg_color, locale.tr("profiles-damaged"));
+                    if ui.button(locale.tr("start-over")).clicked() {
+                        self.profiles.start_list_over();
+                    }
+                });
+            }
+            let profiles: Vec<(String, Option<String>)> = (self.profiles.all().iter())
+                .map(|profile| (profile.name.clone(), profile.damaged().map(str::to_owned)))
+                .collect();
+            let playable = profiles.iter().filter(|(_, damaged)| damaged.is_none());
+            let can_delete_current = playable.count() > 1;
+            for (index, (name, damaged)) in profiles.iter().enumerate() {
+                locale.row(ui, |ui| {
+                    let current = index == self.profiles.current_index();
+                    let label = egui::SelectableLabel::new(current, name);
+                    if ui.add_enabled(damaged.is_none(), label).clicked() {
+                        self.select_profile(index);
+                    }
+                    if let Some(damaged) = damaged {
+                        ui.colored_label(ui.visuals().error_fg_color, locale.tr("profile-damaged"))
+                            .on_hover_text(damaged);
+                        if ui.button(locale.tr("start-over")).clicked() {
+                            self.profiles.start_over(index);
+                        }
+                    }
+                    if ui.button(locale.tr("rename")).clicked() {
+                        self.renaming = Some(index);
+                        self.profile_name = name.clone();
+                    }
+                    if self.deleting == Some(index) {
+                        ui.label(
+                            locale.tr_with("delete-profile-confirm", &[("name", name.into())]),
+                        );
+                        if ui.button(locale.tr("delete")).clicked() {
+                            if let Err(err) = self.delete_profile(index) {
+                                warn!("{err}");
+                            }
+                            self.deleting = None;
+                            self.renaming = None;
+                        }
+                        if ui.button(locale.tr("cancel")).clicked() {
+                            self.deleting = None;
+                        }
+                    } else if ui
+                        .add_enabled(
+                            !current || can_delete_current,
+                            egui::Button::new(locale.tr("delete")),
+                        )
+                        .clicked()
+                    {
+                        self.deleting = Some(index);
+                    }
+                });
+            }
 
-        if ((self.tick - (self.tick % 7)) % 2) == 0 {
-            render::draw_dino_right(self, x, y, painter.clone(), ui, ctx)?;
-        } else {
-            render::draw_dino_left(self, x, y, painter.clone(), ui, ctx)?;
+            let problem = self
+                .profiles
+                .name_problem(&self.profile_name, self.renaming);
+            locale.row(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.profile_name)
+                        .hint_text(locale.tr("profile-name"))
+                        .char_limit(MAX_NAME_LENGTH),
+                );
+                let action = match self.renaming {
+                    Some(_) => locale.tr("rename"),
+                    None => locale.tr("create-profile"),
+                };
+                if ui
+                    .add_enabled(problem.is_none(), egui::Button::new(action))
+                    .clicked()
+                {
+                    let name = std::mem::take(&mut self.profile_name);
+                    let done = match self.renaming.take() {
+                        Some(index) => self.profiles.rename(index, &name),
+                        None => self.create_profile(&name),
+                    };
+                    if let Err(err) = done {
+                        warn!("{err}");
+                    }
+                }
+                if self.renaming.is_some() && ui.button(locale.tr("cancel")).clicked() {
+                    self.renaming = None;
+                    self.profile_name.clear();
+                }
+            });
+            if let Some(problem) = problem.filter(|_| !self.profile_name.is_empty()) {
+                ui.colored_label(ui.visuals().error_fg_color, locale.tr(problem));
+            }
+
+            ui.heading("\n\n");
+            if ui.button(locale.tr("go-back")).clicked() {
+                self.state = AppStatus::Menu;
+            }
+        });
+    }
+
+    /// Displays today's daily challenge status and the best official attempts
+    fn show_daily(&self, ui: &mut Ui) {
+        let locale = &self.locale;
+        match self.daily.result(mode::today()) {
+            Some(score) => {
+                let score = (score / 85.0) as i32;
+                ui.label(locale.tr_with("daily-official-score", &[("score", score.into())]))
+            }
+            None if self.settings.standard_rules() => {
+                ui.label(locale.tr("daily-next-run-official"))
+            }
+            None => ui.label(locale.tr("daily-needs-standard-rules")),
+        };
+
+        let leaderboard = self.daily.leaderboard();
+        if leaderboard.is_empty() {
+            return;
         }
-        Ok(())
+        ui.label(locale.tr("daily-leaderboard"));
+        egui::Grid::new("daily_leaderboard").show(ui, |ui| {
+            for (place, result) in leaderboard.iter().take(5).enumerate() {
+                ui.label(format!("{}.", place + 1));
+                ui.label(mode::format_day(result.day));
+                ui.label(((result.score / 85.0) as i32).to_string());
+                ui.end_row();
+            }
+        });
     }
 
-    fn draw_enemy(
-        &mut self,
-        enemy: Enemy,
-        painter: Painter,
-        ui: &mut Ui,
-        ctx: &eframe::egui::Context,
-    ) -> Result<()> {
-        //render::draw_enemy(enemy, &painter)?;
-        render::draw_cacti_small(
-            self,
-            enemy.start_x * (render::SIZE as f64),
-            271.0 * (render::SIZE as f64),
-            &painter,
-            ui,
-            ctx,
-        )?;
-        Ok(())
+    /// Displays the controls for joining an online race
+    fn show_online(&mut self, ui: &mut Ui) {
+        let locale = self.locale.clone();
+        ui.collapsing(locale.tr("race-online"), |ui| {
+            egui::Grid::new("online").show(ui, |ui| {
+                ui.label(locale.tr("relay"));
+                ui.text_edit_singleline(&mut self.relay);
+                ui.end_row();
+                ui.label(locale.tr("room"));
+                ui.text_edit_singleline(&mut self.room);
+                ui.end_row();
+            });
+            ui.label(locale.tr("online-uses-classic-rules"));
+            if let Some(error) = &self.online_error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+            let problem = net::room_problem(&self.room);
+            if ui
+                .add_enabled(
+                    problem.is_none(),
+                    egui::Button::new(locale.tr("find-opponent")),
+                )
+                .on_disabled_hover_text(problem.map(|id| locale.tr(id)).unwrap_or_default())
+                .clicked()
+            {
+                self.join_online();
+            }
+        });
     }
 
-    fn tick_game(&mut self, ui: &mut Ui) -> Result<()> {
-        self.dino_speed += 0.02;
+    /// Displays the waiting screen while the relay looks for an opponent
+    fn update_waiting(&mut self, ui: &mut Ui) {
+        let locale = self.locale.clone();
+        ui.vertical_centered(|ui| {
+            let room = self.room.as_str().into();
+            ui.heading(locale.tr_with("waiting-for-opponent", &[("room", room)]));
+            ui.spinner();
+            if ui.button(locale.tr("cancel")).clicked() {
+                self.leave_online(None);
+            }
+        });
 
-        if self.intro_mode == 1 && self.dino_y == 100.0 {
-            self.tick += 1;
-            self.intro_mode = 2;
+        let Some(session) = &mut self.online else {
+            return;
         };
+        for event in session.poll() {
+            match event {
+                Event::Start { seed, players, .. } => self.start_online(seed, players),
+                Event::Disconnected(reason) => self.leave_online(Some(reason)),
+                Event::Left { .. } => {}
+            }
+        }
+    }
 
-        if self.intro_mode == 0 && self.dino_y != 100.0 {
-            self.intro_mode = 1;
+    /// Displays the settings
+    fn update_settings(
+        &mut self,
+        _ctx: &eframe::egui::Context,
+        _frame: &mut eframe::Frame,
+        ui: &mut Ui,
+    ) {
+        let language = self.settings.language;
+        let locale = self.locale.clone();
+        ui.vertical_centered(|ui| {
+            ui.heading(locale.tr("settings"));
+            self.settings.show(ui, &locale);
+            self.show_save_data(ui);
+            ui.heading("\n\n");
+            if ui.button(locale.tr("go-back")).clicked() {
+                self.state = AppStatus::Menu;
+            }
+        });
+        if self.settings.language != language {
+            self.locale = Locale::new(self.settings.language());
         }
+    }
 
-        //enemy spawning
-        if self.tick > 0 || self.dino_y < 100.0 {
-            self.tick += 1;
-            self.intro_mode += 20;
-            self.dino_distance += self.dino_speed * 0.3;
+    /// Displays the controls for moving progress to another device
+    fn show_save_data(&mut self, ui: &mut Ui) {
+        let locale = self.locale.clone();
+        ui.collapsing(locale.tr("save-data"), |ui| {
+            ui.label(locale.tr("save-data-hint"));
+            locale.row(ui, |ui| {
+                if ui.button(locale.tr("copy-save-code")).clicked() {
+                    self.save_status = Some(match self.save_data().to_code() {
+                        Ok(code) => {
+                            ui.ctx().copy_text(code);
+                            locale.tr("save-code-copied")
+                        }
+                        Err(err) => {
+                            let error = err.to_string();
+                            locale.tr_with("save-export-failed", &[("error", error.into())])
+                        }
+                    });
+                }
+                if ui.button(locale.tr("export-save")).clicked() {
+                    let exported = self
+                        .save_data()
+                        .to_text()
+                        .and_then(|text| clip::save_file(save::FILE_NAME, text.into_bytes()));
+                    self.save_status = Some(match exported {
+                        Ok(()) => {
+                            locale.tr_with("save-exported", &[("file", save::FILE_NAME.into())])
+                        }
+                        Err(err) => {
+                            let error = err.to_string();
+                            locale.tr_with("save-export-failed", &[("error", error.into())])
+                        }
+                    });
+                }
+            });
+            ui.add(
+                egui::TextEdit::singleline(&mut self.save_code)
+                    .hint_text(locale.tr("save-code-hint")),
+            );
+            if ui
+                .button(locale.tr("import-save"))
+                .on_hover_text(locale.tr("import-save-hint"))
+                .clicked()
+            {
+                let code = std::mem::take(&mut self.save_code);
+                self.import(&code);
+            }
+            if let Some(status) = &self.save_status {
+                ui.label(status);
+            }
+        });
 
-            if self.cooldown == 0 {
-                let mut rng = rand::rng();
-                let chance = rng.random_range(1..=1300);
-                if chance <= 31 || self.enemys.is_empty() && chance <= 100 {
-                    self.enemys.push(Enemy::default());
-                    self.cooldown = 32;
+        // save files dropped on the window are imported too, the web gets their bytes and
+        // native gets their path
+        let dropped = ui.ctx().input(|i| i.raw.dropped_files.first().cloned());
+        if let Some(file) = dropped {
+            let bytes = match (file.bytes, file.path) {
+                (Some(bytes), _) => Ok(bytes.to_vec()),
+                (None, Some(path)) => std::fs::read(path),
+                (None, None) => Ok(Vec::new()),
+            };
+            match bytes {
+                Ok(bytes) => self.import(&String::from_utf8_lossy(&bytes)),
+                Err(err) => {
+                    let error = err.to_string();
+                    let failed = self
+                        .locale
+                        .tr_with("save-import-failed", &[("error", error.into())]);
+                    self.save_status = Some(failed);
                 }
             }
         }
-        if self.cooldown != 0 {
-            self.cooldown -= 1;
+    }
+
+    /// Replaces the player's progress with a save file or save code.
+    fn import(&mut self, text: &str) {
+        self.save_status = Some(match SaveData::from_text(text) {
+            Ok(save) => {
+                self.load_save(save);
+                self.locale.tr("save-imported")
+            }
+            Err(err) => {
+                let error = err.to_string();
+                self.locale
+                    .tr_with("save-import-failed", &[("error", error.into())])
+            }
+        });
+    }
+
+    /// Everything about the player worth keeping when the app closes.
+    pub fn save_data(&self) -> SaveData {
+        let mut best_scores = self.best_scores.clone();
+        best_scores.insert(self.mode, self.high_score);
+        SaveData {
+            version: SAVE_VERSION,
+            mode: self.mode,
+            best_scores,
+            assisted_scores: self.assisted_scores.clone(),
+            daily: self.daily.clone(),
+            history: self.history.clone(),
+            two_player: self.two_player,
+            bot_opponent: self.bot_opponent,
+            relay: self.relay.clone(),
+            room: self.room.clone(),
+            settings: self.settings.clone(),
         }
+    }
 
-        // gravity
-        if self.dino_y < 100.0 {
-            self.dino_speed_y += 1.2;
-        } else {
-            self.dino_y = 100.0;
-            self.dino_speed_y = 0.0_f64.min(self.dino_speed_y);
-        };
-        self.dino_y = (100_f64).min(self.dino_y + self.dino_speed_y);
+    /// Everyone who plays on this machine.
+    pub fn profiles(&self) -> &Profiles {
+        &self.profiles
+    }
 
-        self.dino_speed += 0.006;
+    /// Copies the progress of the profile being played back into the list of profiles.
+    fn stash_profile(&mut self) {
+        self.profiles.current_mut().save = self.save_data();
+    }
 
-        let mut kill = Vec::new();
-        for enemy in self.enemys.iter_mut() {
-            enemy.start_x -= self.dino_speed * 0.3;
-            enemy.end_x -= self.dino_speed * 0.3;
+    /// Switches to the profile at `index`, putting its progress in place of the current one's.
+    pub fn select_profile(&mut self, index: usize) {
+        self.stash_profile();
+        self.profiles.select(index);
+        self.load_save(self.profiles.current().save.clone());
+    }
 
-            // if the enemy is off screen, remove it to save resources
-            if enemy.end_x < -80.0 {
-                enemy.ignore = true;
-                kill.push(*enemy);
-            }
+    /// Adds a profile called `name` and switches to it.
+    pub fn create_profile(&mut self, name: &str) -> Result<()> {
+        self.stash_profile();
+        self.profiles.create(name)?;
+        self.load_save(self.profiles.current().save.clone());
+        Ok(())
+    }
+
+    /// Deletes the profile at `index` with all its progress.
+    pub fn delete_profile(&mut self, index: usize) -> Result<()> {
+        self.stash_profile();
+        self.profiles.delete(index)?;
+        self.load_save(self.profiles.current().save.clone());
+        Ok(())
+    }
 
-            if (self.dino_y >= 54.0) & ((enemy.start_x < 105.0) & (enemy.end_x > -15.0)) {
-                self.state = AppStatus::Died;
+    /// Puts back everything from a save, leaving what is going on right now alone.
+    pub fn load_save(&mut self, save: SaveData) {
+        self.high_score = save.best_scores.get(&save.mode).copied().unwrap_or(0.0);
+        self.mode = save.mode;
+        self.best_scores = save.best_scores;
+        self.assisted_scores = save.assisted_scores;
+        self.daily = save.daily;
+        self.history = save.history;
+        self.two_player = save.two_player;
+        self.bot_opponent = save.bot_opponent;
+        self.relay = save.relay;
+        self.room = save.room;
+        self.settings = save.settings;
+        self.locale = Locale::new(self.settings.language());
+    }
+
+    /// Advances the game by one tick with the given buttons held.
+    pub fn step(&mut self, input: Input) -> Result<()> {
+        self.step_players(&[input])
+    }
+
+    /// Advances every player's run by one tick, with the buttons each player holds. Players
+    /// missing from `inputs` hold nothing.
+    pub fn step_players(&mut self, inputs: &[Input]) -> Result<()> {
+        let (settings, mode) = self.rules();
+        for (player, run) in self.runs.iter_mut().enumerate() {
+            if !run.is_finished() {
+                let input = inputs.get(player).copied().unwrap_or_default();
+                run.step(input, &settings, mode)?;
+                if let Some(replay) = &mut self.replay {
+                    replay.inputs.push(input);
+                }
             }
         }
+        if self.runs.iter().all(Run::is_finished) {
+            self.end_run();
+        }
+        Ok(())
+    }
 
-        for to_rem in kill {
-            if let Some(index) = self.enemys.iter().position(|value| *value == to_rem) {
-                self.enemys.swap_remove(index);
-            }
+    /// Advances the game by one tick, asking each player's controller for their buttons.
+    pub fn step_controllers(&mut self, ui: Option<&Ui>) -> Result<()> {
+        let inputs: Vec<Input> = self
+            .controllers
+            .iter_mut()
+            .zip(&self.runs)
+            .map(|(controller, run)| controller.act(&View { run, ui }))
+            .collect();
+        self.step_players(&inputs)
+    }
+
+    fn tick_game(&mut self, ui: &mut Ui) -> Result<()> {
+        if self.online.is_some() {
+            return self.tick_online(ui);
         }
+        self.step_controllers(Some(ui))
+    }
 
-        let events = ui.input(|i| i.clone()).events.clone();
-        for event in &events {
+    /// Sends this player's buttons to the other players, then simulates every tick everyone's
+    /// buttons have arrived for.
+    fn tick_online(&mut self, ui: &mut Ui) -> Result<()> {
+        let Some(session) = &mut self.online else {
+            return Ok(());
+        };
+        let input = match (session.player(), &mut self.controllers[..]) {
+            (Some(local), controllers) if local < controllers.len() => {
+                let run = &self.runs[local];
+                controllers[local].act(&View { run, ui: Some(ui) })
+            }
+            _ => Input::default(),
+        };
+        let events = session.poll();
+        let ticks = session.tick(input);
+
+        for event in events {
             match event {
-                egui::Event::Key { key, .. } => {
-                    if *key == Key::W || *key == Key::ArrowUp {
-                        Self::jump(self)?;
-                    }
-                }
-                egui::Event::Text(t) => {
-                    if t == "W" || t == " " {
-                        Self::jump(self)?;
+                Event::Left { player } => {
+                    if let Some(run) = self.runs.get_mut(player) {
+                        run.finished = true;
                     }
                 }
-                egui::Event::PointerButton { pos, pressed, .. } => {
-                    if !pressed {
-                        continue;
-                    };
-                    if pos.x < 25.0 || pos.x > 1502.0 {
-                        continue;
-                    };
-                    if pos.y < 108.0 || pos.y > 327.0 {
-                        continue;
-                    };
-                    Self::jump(self)?;
+                Event::Disconnected(reason) => {
+                    self.leave_online(Some(reason));
+                    return Ok(());
                 }
-                _ => {}
+                Event::Start { .. } => {}
             }
         }
-
+        for inputs in ticks {
+            if self.is_dead() {
+                break;
+            }
+            self.step_players(&inputs)?;
+        }
         Ok(())
     }
 
+    /// Whether jump has just been pressed, for the screens around the game.
+    fn jump_pressed(&mut self, ui: &Ui) -> bool {
+        let input = Input::read(ui, &self.settings.keys);
+        let pressed = input.jump && !self.held.jump;
+        self.held = input;
+        pressed
+    }
+
     fn update_game(
         &mut self,
-        ctx: &eframe::egui::Context,
+        _ctx: &eframe::egui::Context,
         _frame: &mut eframe::Frame,
         ui: &mut Ui,
     ) -> Result<()> {
-        ui.heading("Dino Game");
+        ui.heading(self.locale.tr("game-heading"));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if ui.input(|i| i.key_pressed(Key::F12)) {
+            self.save_screenshot();
+        }
 
-        let (_, painter) = ui.allocate_painter(
-            egui::vec2(1300.0_f32.min(200.0 + (self.intro_mode as f32)), 300.0),
+        if ui.input(|i| i.key_pressed(Key::Enter)) {
+            self.runs.iter_mut().for_each(Run::skip_intro);
+        }
+
+        let reveal = self
+            .runs
+            .iter()
+            .map(|run| run.intro().reveal())
+            .fold(0.0, f32::max);
+        let local = self.online.as_ref().and_then(Session::player);
+        let lanes = if local.is_some() {
+            1.0
+        } else {
+            self.runs.len() as f32
+        };
+        let (response, painter) = ui.allocate_painter(
+            egui::vec2(
+                1300.0,
+                300.0 + (lanes - 1.0) * (LANE_HEIGHT as f32) * render::SIZE,
+            ),
             Sense::drag(),
         );
-        if self.high_score < self.dino_distance {
-            self.high_score = self.dino_distance;
+        self.narrate(ui, &response, local);
+
+        // the intro wipes the play field into view from behind the dino
+        let mut shown = response.rect;
+        shown.set_width(INTRO_WIDTH + (shown.width() - INTRO_WIDTH) * reveal);
+        let painter = painter.with_clip_rect(painter.clip_rect().intersect(shown));
+        let theme = self.settings.theme();
+        painter.rect_filled(shown, 0.0, theme.background);
+        self.renderer.set_theme(theme);
+        if self.official && self.high_score < self.runs[0].score {
+            self.high_score = self.runs[0].score;
         };
-        // scoreboard
-        render::draw_numbers(
-            ((self.dino_distance / 85.0) as i32).to_string(),
-            self,
-            1500.0 * (render::SIZE as f64),
-            210.0 * render::SIZE as f64,
-            &painter.clone(),
-            ui,
-            &mut ctx.clone(),
-            false,
-        )?;
-
-        render::draw_numbers(
-            String::from(&((self.high_score / 85.0) as i32).to_string()),
-            self,
-            1350.0 * (render::SIZE as f64),
-            210.0 * render::SIZE as f64,
-            &painter.clone(),
-            ui,
-            &mut ctx.clone(),
-            true,
-        )?;
-
-        for enemy in (self.enemys).clone().iter_mut() {
-            if !enemy.ignore {
-                Self::draw_enemy(self, *enemy, painter.clone(), ui, ctx)?;
-            }
-        }
-
-        if self.state == AppStatus::PlayingGame || self.state == AppStatus::Died {
-            Self::draw_dino(self, 30.0, self.dino_y + 150.0, &painter.clone(), ui, ctx)?;
-        } else {
-            Self::draw_dino_rest(self, 30.0, self.dino_y + 150.0, painter.clone(), ui, ctx)?;
+
+        let shake = self.death.as_ref().map_or(Vec2::ZERO, DeathSequence::shake)
+            * self.settings.effects.scale();
+        self.renderer.set_offset(shake);
+        self.draw(&painter, |game, renderer| {
+            game.draw_lanes(renderer, local, &painter)?;
+            if let Some(death) = &game.death {
+                let tint = Color32::WHITE.gamma_multiply(death.panel());
+                render::draw_game_over(renderer, 0.0, tint);
+            }
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    /// Puts anything new worth saying about this player's run in a live region on the play
+    /// field, for screen readers.
+    fn narrate(&mut self, ui: &Ui, play_field: &Response, local: Option<usize>) {
+        let run = &self.runs[local.unwrap_or(0)];
+        let said = self.narrator.update(run, &self.locale);
+        if !said.is_empty() {
+            self.announcement = said.join(" ");
+        }
+        let urgent = run.is_finished();
+        ui.ctx().accesskit_node_builder(play_field.id, |node| {
+            node.set_role(accesskit::Role::Status);
+            node.set_live(if urgent {
+                accesskit::Live::Assertive
+            } else {
+                accesskit::Live::Polite
+            });
+            node.set_description(self.locale.speak_with("play-field", &[]));
+            node.set_label(self.announcement.as_str());
+        });
+    }
+
+    /// Draws every lane, or just this player's lane with the other players as ghosts in an
+    /// online race.
+    fn draw_lanes(
+        &self,
+        renderer: &mut Renderer,
+        local: Option<usize>,
+        painter: &Painter,
+    ) -> Result<()> {
+        match local {
+            // online opponents are ghosts in this player's lane
+            Some(local) => {
+                self.draw_lane(renderer, &self.runs[local], 0.0, None, painter)?;
+                for player in (0..self.runs.len()).filter(|player| *player != local) {
+                    let run = &self.runs[player];
+                    render::draw_dino(renderer, run, &sprite::dino(run), 0.0, GHOST_TINT);
+                }
+                Ok(())
+            }
+            None => {
+                let labelled = self.runs.len() > 1;
+                for (player, run) in self.runs.iter().enumerate() {
+                    let label = labelled.then(|| {
+                        let player = player + 1;
+                        self.locale
+                            .tr_with("lane-label", &[("player", player.into())])
+                    });
+                    let y = player as f64 * LANE_HEIGHT;
+                    self.draw_lane(renderer, run, y, label, painter)?;
+                }
+                Ok(())
+            }
         }
+    }
 
-        if self.tick > 0 {
-            render::draw_floor(
-                self,
-                (30.0 + 2400.0 - self.dino_distance % 2400.0 - 20.0) * (render::SIZE as f64),
-                (320.0 * render::SIZE).into(),
-                &painter.clone(),
-                ui,
-                ctx,
-            )?;
-            render::draw_floor(
-                self,
-                (30.0 - self.dino_distance % 2400.0) * (render::SIZE as f64),
-                (320.0 * render::SIZE).into(),
-                &painter.clone(),
-                ui,
-                ctx,
-            )?;
+    /// Draws with the renderer, which gets the game read-only, then hands the sprites drawn to
+    /// `painter`.
+    fn draw(
+        &mut self,
+        painter: &Painter,
+        draw: impl FnOnce(&Self, &mut Renderer) -> Result<()>,
+    ) -> Result<()> {
+        let mut renderer = std::mem::take(&mut self.renderer);
+        let drawn = draw(self, &mut renderer).and_then(|()| renderer.finish(painter));
+        self.renderer = renderer;
+        drawn
+    }
+
+    /// Draws a run `y` below where a single player's run goes. Races label each lane instead of
+    /// showing the high score.
+    fn draw_lane(
+        &self,
+        renderer: &mut Renderer,
+        run: &Run,
+        y: f64,
+        label: Option<String>,
+        painter: &Painter,
+    ) -> Result<()> {
+        let attract = self.state == AppStatus::Menu;
+
+        if let Some(label) = label {
+            painter.text(
+                Pos2::new(1450.0 * render::SIZE, (222.0 + y as f32) * render::SIZE),
+                Align2::RIGHT_CENTER,
+                label,
+                FontId::monospace(20.0 * render::SIZE),
+                self.settings.theme().foreground,
+            );
+        } else if !attract {
+            render::draw_numbers(
+                renderer,
+                &((self.high_score / 85.0) as i32).to_string(),
+                1350.0 * (render::SIZE as f64),
+                (210.0 + y) * render::SIZE as f64,
+                true,
+                Color32::WHITE,
+            );
+        }
+
+        if self.mode == GameMode::TimeAttack && !attract {
+            let ticks_left = TIME_ATTACK_TICKS.saturating_sub(run.run_ticks);
+            render::draw_numbers(
+                renderer,
+                &ticks_left.div_ceil(60).to_string(),
+                1200.0 * (render::SIZE as f64),
+                (210.0 + y) * render::SIZE as f64,
+                false,
+                Color32::WHITE,
+            );
         }
 
+        render::draw_run(renderer, run, y, &sprite::dino(run), Color32::WHITE);
+
         Ok(())
     }
 
     fn ready(&mut self, ui: &mut Ui) {
-        ui.heading("Ready?");
-        ui.heading("Click or press space, w or up arrow to start.");
-        let events = ui.input(|i| i.clone()).events.clone();
-        for event in &events {
-            match event {
-                egui::Event::Key { key, .. } => {
-                    if *key == Key::W || *key == Key::ArrowUp {
-                        self.state = AppStatus::PlayingGame;
-                        let _ = Self::jump(self);
-                    }
-                }
-                egui::Event::Text(t) => {
-                    if t == "W" || t == " " {
-                        self.state = AppStatus::PlayingGame;
-                        let _ = Self::jump(self);
-                    }
-                }
-                egui::Event::PointerButton { pos, pressed, .. } => {
-                    if !pressed {
-                        continue;
-                    };
-                    if pos.x < 25.0 || pos.x > 1502.0 {
-                        continue;
-                    };
-                    if pos.y < 108.0 || pos.y > 327.0 {
-                        continue;
-                    };
-                    self.state = AppStatus::PlayingGame;
-                    let _ = Self::jump(self);
-                }
-                _ => {}
+        let locale = &self.locale;
+        ui.with_layout(locale.layout(), |ui| {
+            ui.heading(locale.tr("ready"));
+            if self.runs.len() > 1 && !self.bot_opponent {
+                ui.heading(locale.tr("ready-player-one"));
+                ui.heading(locale.tr("ready-player-two"));
+            } else {
+                ui.heading(locale.tr("ready-start"));
+            }
+            if self.settings.play_intro {
+                ui.label(locale.tr("ready-skip-intro"));
             }
+        });
+        if self.jump_pressed(ui) {
+            self.start();
+        } else {
+            self.runs.iter_mut().for_each(Run::idle);
         }
     }
 
+    /// Checks on the clip being saved, and says where it went once it is done.
+    fn poll_clip(&mut self, ctx: &egui::Context) {
+        let Some(saving) = &self.saving_clip else {
+            return;
+        };
+        let Some(saved) = saving.poll() else {
+            ctx.request_repaint();
+            return;
+        };
+        self.saving_clip = None;
+        self.clip_status = Some(match saved {
+            Ok(files) => self
+                .locale
+                .tr_with("clip-saved", &[("files", files.into())]),
+            Err(err) => {
+                let error = err.to_string();
+                self.locale
+                    .tr_with("clip-failed", &[("error", error.into())])
+            }
+        });
+    }
+
     fn update_death(
         &mut self,
         _ctx: &eframe::egui::Context,
         _frame: &mut eframe::Frame,
         ui: &mut Ui,
     ) -> Result<()> {
-        ui.heading("You died, play again?\n");
-        if ui.button("Return to main menu").clicked() {
-            self.state = AppStatus::Menu;
+        let locale = &self.locale;
+        let heading = if !self.mode.can_die() {
+            "run-finished"
+        } else if self.mode == GameMode::TimeAttack && self.runs[0].run_ticks >= TIME_ATTACK_TICKS {
+            "times-up"
+        } else {
+            "you-died"
         };
-        ui.heading("");
-
-        let input = ui.input(|i| i.clone());
-        let events = input.events.clone();
-        let mouse_position = input.pointer.latest_pos();
-        for event in &events {
-            match event {
-                egui::Event::Key { key, .. } => {
-                    if *key == Key::W || *key == Key::ArrowUp {
-                        *self = DinoGame::default();
-                        self.state = AppStatus::PlayingGame;
-                        let _ = Self::jump(self);
-                    }
-                    if *key == Key::G {
-                        warn!(
-                            "{}",
-                            mouse_position
-                                .or_else(|| Some(Pos2 { x: -1.0, y: -1.0 }))
-                                .unwrap()
-                        );
-                    }
+        let again = ui
+            .with_layout(locale.layout(), |ui| {
+                ui.heading(locale.tr(heading) + "\n");
+                if self.settings.assisted() {
+                    ui.label(locale.tr("assisted-run"));
+                } else if self.settings.power_ups {
+                    ui.label(locale.tr("power-ups-run"));
+                } else if self.mode == GameMode::Daily && !self.official {
+                    ui.label(locale.tr("practice-run"));
                 }
-                egui::Event::Text(t) => {
-                    if t == "W" || t == " " {
-                        *self = DinoGame::default();
-                        self.state = AppStatus::PlayingGame;
-                        let _ = Self::jump(self);
+                let again = ui.button(locale.tr("play-again")).clicked();
+                if ui.button(locale.tr("main-menu")).clicked() {
+                    self.state = AppStatus::Menu;
+                };
+                if let Some(replay) = &self.replay {
+                    if ui
+                        .button(locale.tr("copy-replay"))
+                        .on_hover_text(locale.tr("copy-replay-hint"))
+                        .clicked()
+                    {
+                        ui.ctx().copy_text(replay.to_text());
+                    }
+                    let save_clip = egui::Button::new(locale.tr("save-clip"));
+                    if ui
+                        .add_enabled(self.saving_clip.is_none(), save_clip)
+                        .clicked()
+                    {
+                        let seconds =
+                            (self.settings.clip_seconds > 0).then_some(self.settings.clip_seconds);
+                        let name = format!("dino-{}-{}", replay.seed, (replay.score / 85.0) as i32);
+                        let format = self.settings.clip_format;
+                        let atlas = self.renderer.atlas().clone();
+                        match clip::Export::new(replay, seconds, format, &name, atlas) {
+                            Ok(export) => {
+                                self.saving_clip = Some(clip::Saving::start(export));
+                                self.clip_status = Some(locale.tr("clip-saving"));
+                            }
+                            Err(err) => {
+                                let error = err.to_string();
+                                self.clip_status =
+                                    Some(locale.tr_with("clip-failed", &[("error", error.into())]));
+                            }
+                        }
                     }
                 }
-                egui::Event::PointerButton { pos, pressed, .. } => {
-                    if !pressed {
-                        continue;
-                    };
-                    if pos.x < 25.0 || pos.x > 1502.0 {
-                        continue;
-                    };
-                    if pos.y < 108.0 || pos.y > 327.0 {
-                        continue;
-                    };
-                    *self = DinoGame::default();
-                    self.state = AppStatus::PlayingGame;
-                    let _ = Self::jump(self);
+                if let Some(status) = &self.clip_status {
+                    ui.label(status);
                 }
-                _ => {}
+                ui.heading("");
+                again
+            })
+            .inner;
+
+        let jumped = self.jump_pressed(ui) && self.accepts_restart();
+        if again || jumped {
+            self.restart();
+        }
+
+        let input = ui.input(|i| i.clone());
+        let mouse_position = input.pointer.latest_pos();
+        for event in &input.events {
+            if let egui::Event::Key {
+                key: Key::G,
+                pressed: true,
+                ..
+            } = event
+            {
+                warn!(
+                    "{}",
+                    mouse_position
+                        .or_else(|| Some(Pos2 { x: -1.0, y: -1.0 }))
+                        .unwrap()
+                );
             }
         }
 
         Ok(())
     }
+
+    /// Displays who won a two player race, in place of the death screen.
+    fn update_results(&mut self, ui: &mut Ui) {
+        let best = self
+            .runs
+            .iter()
+            .map(Run::score)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let winners: Vec<usize> = (0..self.runs.len())
+            .filter(|player| self.runs[*player].score == best)
+            .collect();
+        let local = self.online.as_ref().and_then(Session::player);
+        let (controllers, locale) = (&self.controllers, &self.locale);
+        // names go into other messages, so they stay in reading order until the end
+        let name = |player: usize| match (local, controllers.get(player)) {
+            (Some(local), _) if local == player => locale.speak_with("you", &[]),
+            (None, Some(controller)) if !controller.is_human() => controller.name().to_owned(),
+            _ => locale.speak_with("player", &[("player", (player + 1).into())]),
+        };
+        let heading = match winners[..] {
+            [winner] if Some(winner) == local => locale.tr("you-win"),
+            [winner] => locale.tr_with("player-wins", &[("player", name(winner).into())]),
+            _ => locale.tr("draw"),
+        };
+        let (again, leave) = ui
+            .with_layout(locale.layout(), |ui| {
+                ui.heading(heading + "\n");
+                for (player, run) in self.runs.iter().enumerate() {
+                    let score = (run.score / 85.0) as i32;
+                    let args = [("player", name(player).into()), ("score", score.into())];
+                    ui.label(locale.tr_with("player-score", &args));
+                }
+                // a rematch online needs a new opponent from the relay
+                let again = self.online.is_none() && ui.button(locale.tr("play-again")).clicked();
+                let leave = ui.button(locale.tr("main-menu")).clicked();
+                ui.heading("");
+                (again, leave)
+            })
+            .inner;
+        if leave {
+            self.leave_online(None);
+        }
+
+        let jumped = self.jump_pressed(ui) && self.accepts_restart();
+        if again || (self.online.is_none() && jumped) {
+            self.restart();
+        }
+    }
 }
 
 impl eframe::App for DinoGame {
     /// Called by the frame work to save state before shutdown.
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, eframe::APP_KEY, self);
+        self.stash_profile();
+        if let Err(err) = self.profiles.store(storage) {
+            warn!("couldn't save: {err}");
+        }
     }
 
     /// Called each time the UI needs repainting, which may be many times per second.
@@ -527,10 +1653,11 @@ impl eframe::App for DinoGame {
         // Put your widgets into a `SidePanel`, `TopBottomPanel`, `CentralPanel`, `Window` or `Area`.
         // For inspiration and more examples, go to https://emilk.github.io/egui
 
-        if self.state == AppStatus::PlayingGame {
+        if self.state == AppStatus::PlayingGame || self.state == AppStatus::WaitingForOpponent {
             // Tell the backend to repaint as soon as possible
             ctx.request_repaint();
         }
+        self.poll_clip(ctx);
 
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             // The top panel is often a good place for a menu bar:
@@ -539,8 +1666,8 @@ impl eframe::App for DinoGame {
                 // NOTE: no File->Quit on web pages!
                 let is_web = cfg!(target_arch = "wasm32");
                 if !is_web {
-                    ui.menu_button("File", |ui| {
-                        if ui.button("Quit").clicked() {
+                    ui.menu_button(self.locale.tr("file"), |ui| {
+                        if ui.button(self.locale.tr("quit")).clicked() {
                             ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                         }
                     });
@@ -554,26 +1681,55 @@ impl eframe::App for DinoGame {
         egui::CentralPanel::default().show(ctx, |ui| {
             if (self.state) == AppStatus::Menu {
                 self.update_menu(ctx, _frame, ui);
+            } else if (self.state) == AppStatus::Settings {
+                self.update_settings(ctx, _frame, ui);
+            } else if (self.state) == AppStatus::Assists {
+                self.update_assists(ui);
+            } else if (self.state) == AppStatus::Profiles {
+                self.update_profiles(ui);
             } else if (self.state) == AppStatus::Credits {
                 self.update_credits(ctx, _frame, ui);
+            } else if (self.state) == AppStatus::WaitingForOpponent {
+                self.update_waiting(ui);
             } else if (self.state) == AppStatus::GameReadyToStart {
                 self.ready(ui);
                 self.update_game(ctx, _frame, ui).unwrap();
             } else if (self.state) == AppStatus::PlayingGame {
                 self.tick_game(ui).unwrap();
                 self.update_game(ctx, _frame, ui).unwrap();
+                if self.online.is_none()
+                    && !self.mode.can_die()
+                    && ui.button(self.locale.tr("finish-run")).clicked()
+                {
+                    self.end_run();
+                }
             } else if (self.state) == AppStatus::Died {
-                self.update_death(ctx, _frame, ui).unwrap();
+                let death = self.death.get_or_insert_with(DeathSequence::default);
+                death.tick();
+                if death.is_playing() {
+                    ctx.request_repaint();
+                }
+                if !death.is_frozen() {
+                    self.runs.iter_mut().for_each(Run::settle);
+                }
+                if self.death.is_some_and(|death| death.is_frozen()) {
+                    ui.heading("");
+                } else if self.runs.len() > 1 {
+                    self.update_results(ui);
+                } else {
+                    self.update_death(ctx, _frame, ui).unwrap();
+                }
                 self.update_game(ctx, _frame, ui).unwrap();
             } else {
                 ui.label("Invalid app state");
             }
 
-            ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
-                powered_by_egui_and_eframe(ui);
+            let side = self.locale.layout().horizontal_align();
+            ui.with_layout(egui::Layout::bottom_up(side), |ui| {
+                powered_by_egui_and_eframe(ui, &self.locale);
                 ui.add(egui::github_link_file!(
                     "https://github.com/voidapex11/dino/blob/main/",
-                    "Source code."
+                    self.locale.tr("source-code")
                 ));
                 egui::warn_if_debug_build(ui);
                 ui.separator();
@@ -582,16 +1738,28 @@ impl eframe::App for DinoGame {
     }
 }
 
-fn powered_by_egui_and_eframe(ui: &mut egui::Ui) {
-    ui.horizontal(|ui| {
-        ui.spacing_mut().item_spacing.x = 0.0;
-        ui.label("Powered by ");
+/// A keyboard controller for each of `players` players, splitting the keyboard between them if
+/// there is more than one.
+fn keyboards(settings: &Settings, players: usize) -> Vec<Box<dyn Controller>> {
+    (0..players)
+        .map(|player| -> Box<dyn Controller> {
+            match settings.race_keys.get(player) {
+                Some(keys) if players > 1 => Box::new(Keyboard::player(player, keys.clone())),
+                _ => Box::new(Keyboard::new(settings.keys.clone())),
+            }
+        })
+        .collect()
+}
+
+fn powered_by_egui_and_eframe(ui: &mut egui::Ui, locale: &Locale) {
+    locale.row(ui, |ui| {
+        ui.spacing_mut().item_spacing.x = 4.0;
+        ui.label(locale.tr("powered-by"));
         ui.hyperlink_to("egui", "https://github.com/emilk/egui");
-        ui.label(" and ");
+        ui.label(locale.tr("powered-by-and"));
         ui.hyperlink_to(
             "eframe",
             "https://github.com/emilk/egui/tree/master/crates/eframe",
         );
-        ui.label(".");
     });
 }
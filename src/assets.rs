@@ -0,0 +1,80 @@
+//! # assets
+//! Loads the asset map the game draws from.
+//!
+//! The asset map is built into the game, so it always starts. An `asset-map.png` in the
+//! working directory replaces it, which is handy for trying out new sprites without a rebuild.
+//! Whichever is used is an [`Atlas`], which the window and the images drawn on the CPU share,
+//! so screenshots and clips show the same sprites as the window.
+
+use crate::sprite::{ATLAS_HEIGHT, ATLAS_WIDTH};
+use anyhow::{bail, Context, Result};
+use image::{ImageReader, RgbaImage};
+use std::io::{Cursor, ErrorKind};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// Where the native app looks for an asset map to use instead of the built in one.
+pub const OVERRIDE_PATH: &str = "asset-map.png";
+
+/// The asset map sprites are drawn from. Clones share the same image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Atlas(Arc<RgbaImage>);
+
+impl Atlas {
+    pub fn new(image: RgbaImage) -> Self {
+        Self(Arc::new(image))
+    }
+
+    /// The asset map built into the game, decoded the first time it is needed.
+    pub fn built_in() -> Self {
+        static BUILT_IN: OnceLock<Atlas> = OnceLock::new();
+        BUILT_IN
+            .get_or_init(|| {
+                Self::new(
+                    decode_atlas(include_bytes!("asset-map.png"))
+                        .expect("the embedded asset map is a valid png"),
+                )
+            })
+            .clone()
+    }
+
+    pub fn image(&self) -> &RgbaImage {
+        &self.0
+    }
+}
+
+impl Default for Atlas {
+    fn default() -> Self {
+        Self::built_in()
+    }
+}
+
+/// Decodes an asset map, checking that the sprites are where the game expects them.
+pub fn decode_atlas(bytes: &[u8]) -> Result<RgbaImage> {
+    let atlas = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()?
+        .decode()?
+        .to_rgba8();
+    let expected = (ATLAS_WIDTH as u32, ATLAS_HEIGHT as u32);
+    if atlas.dimensions() != expected {
+        bail!(
+            "the asset map is {}x{}, it should be {}x{}",
+            atlas.width(),
+            atlas.height(),
+            expected.0,
+            expected.1
+        );
+    }
+    Ok(atlas)
+}
+
+/// The asset map at `path`, or `None` if there isn't one.
+pub fn load_override(path: &Path) -> Result<Option<RgbaImage>> {
+    match std::fs::read(path) {
+        Ok(bytes) => decode_atlas(&bytes)
+            .map(Some)
+            .with_context(|| format!("{} can't be used", path.display())),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("couldn't read {}", path.display())),
+    }
+}
@@ -0,0 +1,156 @@
+//! # assist
+//! Options that make the game easier to play.
+//!
+//! The game speed and the longer jump buffer change how a run plays, so they are part of the
+//! [`Settings`](crate::settings::Settings) a replay is played back with. One-switch play and
+//! auto-jump only change which buttons are held, so they wrap the player's controller instead
+//! and replays record the buttons they chose.
+//!
+//! Runs played with any assist on never replace the best score, and are marked in the history.
+//! So are runs with a more forgiving jump buffer or coyote time than the default, see
+//! [`Settings::assisted`](crate::settings::Settings::assisted).
+
+use crate::controller::{Autopilot, Controller, View};
+use crate::input::Input;
+use crate::locale::Locale;
+use crate::run::Run;
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Assists {
+    /// Whether one button does everything: pressing it on the floor jumps, pressing it in the
+    /// air drops back down.
+    pub one_switch: bool,
+
+    /// Whether the [`Autopilot`] jumps over obstacles when the player doesn't, leaving ducking
+    /// and dropping back down to them.
+    pub auto_jump: bool,
+
+    /// How fast the game scrolls, as a percentage of normal speed from 50 to 100.
+    pub game_speed: u32,
+
+    /// Extra ticks a jump pressed in the air is remembered for, on top of the usual buffer.
+    pub jump_buffer: u32,
+}
+
+impl Default for Assists {
+    fn default() -> Self {
+        Self {
+            one_switch: false,
+            auto_jump: false,
+            game_speed: 100,
+            jump_buffer: 0,
+        }
+    }
+}
+
+impl Assists {
+    /// Whether any assist is on.
+    pub fn any(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Whether the player's controller has to be wrapped in [`Assisted`].
+    pub fn changes_buttons(&self) -> bool {
+        self.one_switch || self.auto_jump
+    }
+
+    /// How fast the game scrolls compared to normal.
+    pub fn speed_factor(&self) -> f64 {
+        self.game_speed.clamp(50, 100) as f64 / 100.0
+    }
+
+    /// Draws the controls for changing the assists.
+    pub fn show(&mut self, ui: &mut egui::Ui, locale: &Locale) {
+        ui.checkbox(&mut self.one_switch, locale.tr("one-switch"))
+            .on_hover_text(locale.tr("one-switch-hint"));
+        ui.checkbox(&mut self.auto_jump, locale.tr("auto-jump"))
+            .on_hover_text(locale.tr("auto-jump-hint"));
+        ui.add(egui::Slider::new(&mut self.game_speed, 50..=100).text(locale.tr("game-speed")));
+        ui.add(
+            egui::Slider::new(&mut self.jump_buffer, 0..=30).text(locale.tr("extra-jump-buffer")),
+        )
+        .on_hover_text(locale.tr("extra-jump-buffer-hint"));
+        if ui.button(locale.tr("assists-off")).clicked() {
+            *self = Self::default();
+        }
+    }
+}
+
+/// What the one switch is doing while it is held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Switch {
+    Jump,
+    Duck,
+}
+
+/// A controller with one-switch play or auto-jump on top.
+#[derive(Clone)]
+pub struct Assisted {
+    inner: Box<dyn Controller>,
+    one_switch: bool,
+
+    /// What the switch was pressed to do, while it is still held.
+    switch: Option<Switch>,
+
+    /// What times the jumps, when auto-jump is on.
+    autopilot: Option<Autopilot>,
+
+    /// Whether the dino is in the air from a jump the autopilot made.
+    auto_jumping: bool,
+}
+
+impl Assisted {
+    pub fn new(inner: Box<dyn Controller>, assists: &Assists) -> Self {
+        Self {
+            inner,
+            one_switch: assists.one_switch,
+            switch: None,
+            autopilot: assists.auto_jump.then(Autopilot::default),
+            auto_jumping: false,
+        }
+    }
+
+    /// Turns any button into a jump on the floor or a drop in the air, held for as long as
+    /// the button is.
+    fn one_switch(&mut self, input: Input, run: &Run) -> Input {
+        let pressed = input.jump || input.duck;
+        self.switch = match (pressed, self.switch) {
+            (false, _) => None,
+            (true, Some(switch)) => Some(switch),
+            (true, None) if run.on_ground() => Some(Switch::Jump),
+            (true, None) => Some(Switch::Duck),
+        };
+        Input {
+            jump: self.switch == Some(Switch::Jump),
+            duck: self.switch == Some(Switch::Duck),
+        }
+    }
+}
+
+impl Controller for Assisted {
+    fn act(&mut self, view: &View<'_>) -> Input {
+        let mut input = self.inner.act(view);
+        if self.one_switch {
+            input = self.one_switch(input, view.run);
+        }
+        if let Some(autopilot) = &mut self.autopilot {
+            // a jump the autopilot starts is held for as long as it would hold it, so it clears
+            // what it was timed for
+            let auto = autopilot.act(view);
+            if view.run.on_ground() {
+                self.auto_jumping = auto.jump && !input.jump;
+            }
+            input.jump |= self.auto_jumping && auto.jump;
+        }
+        input
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn is_human(&self) -> bool {
+        self.inner.is_human()
+    }
+}
@@ -0,0 +1,107 @@
+//! # batch
+//! Collects every sprite drawn in a frame into one mesh, so egui gets one draw call for them
+//! instead of one each.
+
+use egui::{Color32, Painter, Pos2, TextureId};
+use epaint::{pos2, Mesh, Rect, Vec2, Vertex};
+
+/// How a sprite is turned and mirrored when it is drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// Clockwise turn around the sprite's centre, in radians.
+    pub rotation: f32,
+
+    /// Mirrors the sprite left to right.
+    pub flip_x: bool,
+
+    /// Mirrors the sprite top to bottom.
+    pub flip_y: bool,
+}
+
+impl Transform {
+    /// Where a point of a sprite at `uv` comes from in the texture once the sprite is mirrored.
+    /// `along` is how far across the unturned sprite the point is, from 0 to 1 on each axis.
+    pub fn uv(&self, uv: Rect, mut along: Vec2) -> Pos2 {
+        if self.flip_x {
+            along.x = 1.0 - along.x;
+        }
+        if self.flip_y {
+            along.y = 1.0 - along.y;
+        }
+        pos2(
+            uv.min.x + uv.width() * along.x,
+            uv.min.y + uv.height() * along.y,
+        )
+    }
+
+    /// Turns `offset` from a sprite's centre by the rotation.
+    pub fn rotate(&self, offset: Vec2) -> Vec2 {
+        let (sin, cos) = self.rotation.sin_cos();
+        Vec2::new(
+            offset.x * cos - offset.y * sin,
+            offset.x * sin + offset.y * cos,
+        )
+    }
+}
+
+/// A frame's worth of sprites from one texture.
+#[derive(Debug, Clone)]
+pub struct SpriteBatch {
+    mesh: Mesh,
+}
+
+impl SpriteBatch {
+    pub fn new(texture: TextureId) -> Self {
+        Self {
+            mesh: Mesh::with_texture(texture),
+        }
+    }
+
+    /// How many sprites are in the batch.
+    pub fn len(&self) -> usize {
+        self.mesh.vertices.len() / 4
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mesh.is_empty()
+    }
+
+    pub fn mesh(&self) -> &Mesh {
+        &self.mesh
+    }
+
+    /// Adds the part of the texture at `uv`, stretched over `rect` and then turned and mirrored
+    /// by `transform`.
+    pub fn push(&mut self, rect: Rect, uv: Rect, tint: Color32, transform: Transform) {
+        let centre = rect.center();
+        let half = rect.size() / 2.0;
+        let first = self.mesh.vertices.len() as u32;
+        for corner in [
+            Vec2::new(-half.x, -half.y),
+            Vec2::new(half.x, -half.y),
+            Vec2::new(-half.x, half.y),
+            Vec2::new(half.x, half.y),
+        ] {
+            self.mesh.vertices.push(Vertex {
+                pos: centre + transform.rotate(corner),
+                uv: transform.uv(uv, corner / rect.size() + Vec2::splat(0.5)),
+                color: tint,
+            });
+        }
+        self.mesh.indices.extend_from_slice(&[
+            first,
+            first + 1,
+            first + 2,
+            first + 2,
+            first + 1,
+            first + 3,
+        ]);
+    }
+
+    /// Hands the whole batch to egui as one shape.
+    pub fn submit(self, painter: &Painter) {
+        if !self.is_empty() {
+            painter.add(self.mesh);
+        }
+    }
+}
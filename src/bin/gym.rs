@@ -0,0 +1,89 @@
+//! Plays many episodes of the training environment as fast as possible.
+//!
+//! Run it with `cargo run --release --bin gym -- [options]`:
+//! - `--episodes <n>` how many runs to play, 1000 by default
+//! - `--policy <random|autopilot>` who plays, the autopilot by default
+//! - `--pixels <scale>` also draw every observation, with `scale` pixels per game unit
+//! - `--max-ticks <n>` end runs after this many ticks, 10000 by default
+//! - `--seed <n>` the seed of the first run, each run after it uses the next one
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> anyhow::Result<()> {
+    use anyhow::{anyhow, Context};
+    use dino::controller::{Autopilot, Controller, View};
+    use dino::env::{Action, Env};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::time::Instant;
+
+    let mut episodes = 1000_u64;
+    let mut autopilot = true;
+    let mut pixels = None;
+    let mut max_ticks = 10_000;
+    let mut first_seed = 0_u64;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().with_context(|| format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--episodes" => episodes = value()?.parse()?,
+            "--policy" => {
+                autopilot = match value()?.as_str() {
+                    "autopilot" => true,
+                    "random" => false,
+                    policy => return Err(anyhow!("unknown policy {policy}")),
+                }
+            }
+            "--pixels" => pixels = Some(value()?.parse()?),
+            "--max-ticks" => max_ticks = value()?.parse()?,
+            "--seed" => first_seed = value()?.parse()?,
+            _ => return Err(anyhow!("unknown option {arg}")),
+        }
+    }
+
+    let mut env = Env::default().with_max_ticks(max_ticks);
+    if let Some(scale) = pixels {
+        env = env.with_pixels(scale);
+    }
+    let mut rng = StdRng::seed_from_u64(first_seed);
+    let mut pilot = Autopilot::default();
+
+    let started = Instant::now();
+    let mut ticks = 0_u64;
+    let mut total = 0.0;
+    let mut best = 0.0_f64;
+    for seed in first_seed..first_seed + episodes {
+        env.reset(seed);
+        let mut score = 0.0;
+        loop {
+            let action = if autopilot {
+                Action::from(pilot.act(&View {
+                    run: env.run(),
+                    ui: None,
+                }))
+            } else {
+                Action::ALL[rng.random_range(0..Action::ALL.len())]
+            };
+            let (_, reward, done) = env.step(action)?;
+            ticks += 1;
+            score += reward;
+            if done {
+                break;
+            }
+        }
+        total += score;
+        best = best.max(score);
+    }
+
+    let seconds = started.elapsed().as_secs_f64();
+    println!("episodes:      {episodes}");
+    println!("mean score:    {:.1}", total / episodes.max(1) as f64);
+    println!("best score:    {best:.1}");
+    println!("episodes/sec:  {:.0}", episodes as f64 / seconds);
+    println!("ticks/sec:     {:.0}", ticks as f64 / seconds);
+    Ok(())
+}
+
+// There is no command line in a browser.
+#[cfg(target_arch = "wasm32")]
+fn main() {}
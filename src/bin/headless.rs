@@ -0,0 +1,188 @@
+//! Runs the game without a window, for scripts and servers.
+//!
+//! `cargo run --release --bin headless -- <command>`, where the command is one of:
+//! - `verify <replay>...` plays back saved replays and checks their scores
+//! - `bot [--seeds <n>] [--first-seed <n>] [--mode <mode>] [--max-ticks <n>] [--save <dir>]`
+//!   lets the autopilot play a run for each seed and prints how its scores are spread
+//! - `bench [--ticks <n>] [--seed <n>]` measures how many ticks a second the game runs at
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> anyhow::Result<()> {
+    cli::main()
+}
+
+// There is no command line in a browser.
+#[cfg(target_arch = "wasm32")]
+fn main() {}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod cli {
+    use anyhow::{anyhow, Context, Result};
+    use dino::controller::Autopilot;
+    use dino::input::Input;
+    use dino::mode::GameMode;
+    use dino::replay::Replay;
+    use dino::run::Run;
+    use dino::settings::Settings;
+    use std::path::PathBuf;
+    use std::time::Instant;
+
+    /// Score is shown to players in these units.
+    const POINT: f64 = 85.0;
+
+    pub fn main() -> Result<()> {
+        let mut args = std::env::args().skip(1);
+        let command = args.next().context("expected verify, bot or bench")?;
+        let args: Vec<String> = args.collect();
+        match command.as_str() {
+            "verify" => verify(&args),
+            "bot" => bot(&args),
+            "bench" => bench(&args),
+            _ => Err(anyhow!("unknown command {command}")),
+        }
+    }
+
+    /// The value after `--name`, if it was given.
+    fn option<T: std::str::FromStr>(args: &[String], name: &str) -> Result<Option<T>>
+    where
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        let Some(index) = args.iter().position(|arg| arg == name) else {
+            return Ok(None);
+        };
+        let value = args
+            .get(index + 1)
+            .with_context(|| format!("{name} needs a value"))?;
+        Ok(Some(value.parse()?))
+    }
+
+    fn verify(paths: &[String]) -> Result<()> {
+        if paths.is_empty() {
+            return Err(anyhow!("expected at least one replay"));
+        }
+        let mut failed = 0;
+        for path in paths {
+            let result = std::fs::read_to_string(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|text| Replay::parse(&text))
+                .and_then(|replay| replay.verify());
+            match result {
+                Ok(run) => println!("ok    {path}: {}", (run.score() / POINT) as u64),
+                Err(err) => {
+                    failed += 1;
+                    println!("FAIL  {path}: {err}");
+                }
+            }
+        }
+        if failed > 0 {
+            return Err(anyhow!("{failed} of {} replays failed", paths.len()));
+        }
+        Ok(())
+    }
+
+    fn bot(args: &[String]) -> Result<()> {
+        let seeds: u64 = option(args, "--seeds")?.unwrap_or(100);
+        let first: u64 = option(args, "--first-seed")?.unwrap_or(0);
+        let max_ticks = option(args, "--max-ticks")?.unwrap_or(100_000);
+        let save: Option<PathBuf> = option(args, "--save")?;
+        let mode = match option::<String>(args, "--mode")? {
+            Some(name) => GameMode::ALL
+                .into_iter()
+                .find(|mode| format!("{mode:?}").eq_ignore_ascii_case(&name.replace('-', "")))
+                .with_context(|| format!("unknown mode {name}"))?,
+            None => GameMode::Classic,
+        };
+        if let Some(dir) = &save {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut scores = Vec::new();
+        for seed in first..first + seeds {
+            let replay = Replay::record(
+                seed,
+                mode,
+                Settings::default(),
+                &mut Autopilot::default(),
+                max_ticks,
+            )?;
+            if let Some(dir) = &save {
+                std::fs::write(dir.join(format!("{seed}.replay")), replay.to_text())?;
+            }
+            scores.push((replay.score / POINT).floor());
+        }
+        print_distribution(&mut scores);
+        Ok(())
+    }
+
+    /// Prints a summary and a histogram of `scores`.
+    fn print_distribution(scores: &mut [f64]) {
+        if scores.is_empty() {
+            return;
+        }
+        scores.sort_by(f64::total_cmp);
+        let percentile = |p: usize| scores[(scores.len() - 1) * p / 100];
+        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        println!("runs    {}", scores.len());
+        println!("mean    {mean:.0}");
+        for (name, p) in [
+            ("min", 0),
+            ("p10", 10),
+            ("median", 50),
+            ("p90", 90),
+            ("max", 100),
+        ] {
+            println!("{name:<7} {:.0}", percentile(p));
+        }
+
+        const BUCKETS: usize = 10;
+        const BAR: usize = 40;
+        let (low, high) = (scores[0], scores[scores.len() - 1]);
+        let width = ((high - low) / BUCKETS as f64).max(1.0);
+        let mut counts = [0_usize; BUCKETS];
+        for score in scores.iter() {
+            counts[(((score - low) / width) as usize).min(BUCKETS - 1)] += 1;
+        }
+        let most = counts.iter().copied().max().unwrap_or(1);
+        println!();
+        for (bucket, count) in counts.iter().enumerate() {
+            let from = low + width * bucket as f64;
+            println!(
+                "{from:>8.0} {:<BAR$} {count}",
+                "#".repeat(count * BAR / most)
+            );
+        }
+    }
+
+    fn bench(args: &[String]) -> Result<()> {
+        let ticks: u64 = option(args, "--ticks")?.unwrap_or(10_000_000);
+        let mut seed: u64 = option(args, "--seed")?.unwrap_or(0);
+        let settings = Settings::default();
+
+        // jumping every so often keeps obstacles spawning and runs short enough to restart
+        let new_run = |seed| {
+            let replay = Replay::new(seed, GameMode::Classic, settings.clone(), Input::default());
+            replay.play()
+        };
+        let mut run: Run = new_run(seed)?;
+        let mut runs = 1;
+        let started = Instant::now();
+        for tick in 0..ticks {
+            let input = Input {
+                jump: tick % 45 == 0,
+                duck: false,
+            };
+            run.step(input, &settings, GameMode::Classic)?;
+            if run.is_finished() {
+                seed += 1;
+                runs += 1;
+                run = new_run(seed)?;
+            }
+        }
+        let seconds = started.elapsed().as_secs_f64();
+        println!("ticks     {ticks}");
+        println!("runs      {runs}");
+        println!("seconds   {seconds:.3}");
+        println!("ticks/sec {:.0}", ticks as f64 / seconds);
+        Ok(())
+    }
+}
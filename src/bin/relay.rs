@@ -0,0 +1,16 @@
+//! The relay server for online races.
+//!
+//! Run it with `cargo run --bin relay -- [address]`, by default it listens on `127.0.0.1:9001`.
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let address = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:9001".to_owned());
+    dino::net::relay::run(&address)
+}
+
+// The relay can't run in a browser.
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -0,0 +1,392 @@
+//! # clip
+//! Turns a run into an animation for sharing.
+//!
+//! Runs are deterministic, so instead of keeping every frame around the run's [`Replay`] is
+//! played back and the frames are drawn on the CPU as they come up. Each frame is encoded as
+//! soon as it is drawn, away from the frames the game is showing.
+
+use crate::assets::Atlas;
+use crate::raster::Raster;
+use crate::replay::Replay;
+use crate::run::Run;
+use anyhow::{anyhow, Result};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, ImageFormat, RgbaImage};
+use std::io::{Cursor, Write};
+use std::sync::{Arc, Mutex};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// How many ticks the game runs a second.
+pub const TICKS_PER_SECOND: u32 = 60;
+
+/// A frame is drawn every this many ticks, giving 20 frames a second.
+pub const TICKS_PER_FRAME: u32 = 3;
+
+/// Pixels per game unit in saved clips.
+pub const SCALE: f32 = 0.5;
+
+/// How many frames the web build saves each update, as it has to save between frames.
+#[cfg(target_arch = "wasm32")]
+const FRAMES_PER_UPDATE: usize = 2;
+
+/// What kind of file a clip is saved as.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub enum ClipFormat {
+    /// One animated GIF.
+    #[default]
+    Gif,
+
+    /// A numbered PNG for each frame.
+    PngSequence,
+}
+
+impl ClipFormat {
+    pub const ALL: [ClipFormat; 2] = [ClipFormat::Gif, ClipFormat::PngSequence];
+
+    /// The formats this build can save. Browsers stop a page starting a download for every
+    /// frame, so the web build only saves GIFs.
+    pub const AVAILABLE: &'static [ClipFormat] = if cfg!(target_arch = "wasm32") {
+        &[ClipFormat::Gif]
+    } else {
+        &Self::ALL
+    };
+
+    /// The id of the format's name in the [`Locale`](crate::locale::Locale).
+    pub fn message_id(self) -> &'static str {
+        match self {
+            ClipFormat::Gif => "clip-format-gif",
+            ClipFormat::PngSequence => "clip-format-png-sequence",
+        }
+    }
+}
+
+/// Draws a replay's frames one at a time, so only one frame is ever kept around.
+pub struct Frames {
+    replay: Replay,
+    run: Run,
+    raster: Raster,
+
+    /// The next tick to play.
+    tick: usize,
+
+    /// The first tick that is drawn.
+    first: usize,
+
+    /// How many ticks are played before the run finishes.
+    ticks: usize,
+
+    /// Whether the frame the run ended on has been drawn.
+    ended: bool,
+}
+
+impl Frames {
+    /// The frames of the last `seconds` of `replay`, or of all of it if `seconds` is `None`.
+    pub fn new(replay: &Replay, seconds: Option<u32>, scale: f32) -> Result<Self> {
+        let ticks = played(replay)?;
+        let mut raster = Raster::new(scale);
+        raster.set_theme(replay.settings.theme());
+        Ok(Self {
+            replay: replay.clone(),
+            run: replay.start(),
+            raster,
+            tick: 0,
+            first: seconds.map_or(0, |seconds| {
+                ticks.saturating_sub((seconds * TICKS_PER_SECOND) as usize)
+            }),
+            ticks,
+            ended: false,
+        })
+    }
+
+    /// Draws from `atlas` instead of the asset map built into the game.
+    pub fn set_atlas(&mut self, atlas: Atlas) {
+        self.raster.set_atlas(atlas);
+    }
+
+    /// Plays on to the next frame and draws it, or returns `None` once the frame the run ended
+    /// on has been drawn.
+    pub fn draw_next(&mut self) -> Result<Option<&RgbaImage>> {
+        while self.tick < self.ticks {
+            let tick = self.tick;
+            let input = self.replay.inputs[tick];
+            self.run
+                .step(input, &self.replay.settings, self.replay.mode)?;
+            self.tick += 1;
+            if tick >= self.first && (tick - self.first) % TICKS_PER_FRAME as usize == 0 {
+                self.ended = self.tick == self.ticks;
+                self.raster.draw_run(&self.run);
+                return Ok(Some(self.raster.image()));
+            }
+        }
+        if self.ended {
+            return Ok(None);
+        }
+        // always end on the frame the run ended on
+        self.ended = true;
+        self.raster.draw_run(&self.run);
+        Ok(Some(self.raster.image()))
+    }
+}
+
+/// How many ticks of `replay` are played before the run finishes.
+fn played(replay: &Replay) -> Result<usize> {
+    let mut run = replay.start();
+    for (tick, input) in replay.inputs.iter().enumerate() {
+        if run.is_finished() {
+            return Ok(tick);
+        }
+        run.step(*input, &replay.settings, replay.mode)?;
+    }
+    Ok(replay.inputs.len())
+}
+
+/// Bytes written by a [`GifEncoder`], which keeps its writer until it is dropped.
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .lock()
+            .expect("nothing panics holding the buffer")
+            .write(bytes)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Buffer {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut self.0.lock().expect("nothing panics holding the buffer"))
+    }
+}
+
+/// Starts a looping GIF.
+fn gif_encoder(buffer: Buffer) -> Result<GifEncoder<Buffer>> {
+    // a faster, rougher palette: the field has few colours anyway
+    let mut encoder = GifEncoder::new_with_speed(buffer, 30);
+    encoder.set_repeat(Repeat::Infinite)?;
+    Ok(encoder)
+}
+
+/// Adds a frame to a GIF.
+fn encode_gif_frame(encoder: &mut GifEncoder<Buffer>, frame: &RgbaImage) -> Result<()> {
+    let delay = Delay::from_numer_denom_ms(1000 * TICKS_PER_FRAME, TICKS_PER_SECOND);
+    encoder.encode_frame(Frame::from_parts(frame.clone(), 0, 0, delay))?;
+    Ok(())
+}
+
+/// Encodes every frame as a looping GIF.
+pub fn encode_gif(mut frames: Frames) -> Result<Vec<u8>> {
+    let buffer = Buffer::default();
+    let mut encoder = gif_encoder(buffer.clone())?;
+    while let Some(frame) = frames.draw_next()? {
+        encode_gif_frame(&mut encoder, frame)?;
+    }
+    // the GIF is finished when the encoder is dropped
+    drop(encoder);
+    Ok(buffer.take())
+}
+
+/// Encodes a frame as a PNG.
+pub fn encode_png(frame: &RgbaImage) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    frame.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)?;
+    Ok(bytes)
+}
+
+/// Where the frames of a clip go as they are drawn.
+enum Output {
+    Gif(GifEncoder<Buffer>, Buffer),
+
+    /// How many PNGs have been saved.
+    Pngs(usize),
+}
+
+/// A clip being drawn, encoded and saved a few frames at a time.
+pub struct Export {
+    frames: Frames,
+    output: Output,
+    name: String,
+}
+
+impl Export {
+    /// Starts a clip of `replay` named after `name`, drawn from `atlas`. Formats this build
+    /// can't save are saved as GIFs instead.
+    pub fn new(
+        replay: &Replay,
+        seconds: Option<u32>,
+        format: ClipFormat,
+        name: &str,
+        atlas: Atlas,
+    ) -> Result<Self> {
+        let output = match format {
+            ClipFormat::PngSequence if ClipFormat::AVAILABLE.contains(&format) => Output::Pngs(0),
+            _ => {
+                let buffer = Buffer::default();
+                Output::Gif(gif_encoder(buffer.clone())?, buffer)
+            }
+        };
+        let mut frames = Frames::new(replay, seconds, SCALE)?;
+        frames.set_atlas(atlas);
+        Ok(Self {
+            frames,
+            output,
+            name: name.to_owned(),
+        })
+    }
+
+    /// Draws and saves up to `count` more frames. Returns where the clip went once it is done.
+    pub fn step(&mut self, count: usize) -> Result<Option<String>> {
+        for _ in 0..count {
+            let Some(frame) = self.frames.draw_next()? else {
+                return self.save().map(Some);
+            };
+            match &mut self.output {
+                Output::Gif(encoder, _) => encode_gif_frame(encoder, frame)?,
+                Output::Pngs(saved) => {
+                    save_file(&format!("{}-{saved:04}.png", self.name), encode_png(frame)?)?;
+                    *saved += 1;
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Draws and saves the rest of the clip. Returns where it went.
+    pub fn finish(mut self) -> Result<String> {
+        loop {
+            if let Some(files) = self.step(1)? {
+                return Ok(files);
+            }
+        }
+    }
+
+    /// Saves whatever is left once every frame is drawn.
+    fn save(&mut self) -> Result<String> {
+        let name = &self.name;
+        match std::mem::replace(&mut self.output, Output::Pngs(0)) {
+            Output::Gif(encoder, buffer) => {
+                // the GIF is finished when the encoder is dropped
+                drop(encoder);
+                let file = format!("{name}.gif");
+                save_file(&file, buffer.take())?;
+                Ok(file)
+            }
+            Output::Pngs(0) => Err(anyhow!("the run has no frames")),
+            Output::Pngs(saved) => Ok(format!("{name}-0000.png to {name}-{:04}.png", saved - 1)),
+        }
+    }
+}
+
+/// Draws and saves a clip of `replay` from `atlas`, naming it after `name`. Returns where it
+/// went.
+pub fn save(
+    replay: &Replay,
+    seconds: Option<u32>,
+    format: ClipFormat,
+    name: &str,
+    atlas: Atlas,
+) -> Result<String> {
+    Export::new(replay, seconds, format, name, atlas)?.finish()
+}
+
+/// A clip saving while the game goes on: on its own thread natively, and a few frames each
+/// update on the web, which has no threads.
+#[derive(Clone)]
+pub struct Saving {
+    #[cfg(not(target_arch = "wasm32"))]
+    saved: Arc<Mutex<Receiver<Result<String>>>>,
+
+    #[cfg(target_arch = "wasm32")]
+    export: std::rc::Rc<std::cell::RefCell<Export>>,
+}
+
+impl Saving {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start(export: Export) -> Self {
+        let (sender, saved) = mpsc::channel();
+        std::thread::spawn(move || {
+            // nobody is left to tell if the game has closed
+            let _ = sender.send(export.finish());
+        });
+        Self {
+            saved: Arc::new(Mutex::new(saved)),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn start(export: Export) -> Self {
+        Self {
+            export: std::rc::Rc::new(std::cell::RefCell::new(export)),
+        }
+    }
+
+    /// Where the clip went once it is saved, or why it couldn't be.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn poll(&self) -> Option<Result<String>> {
+        let saved = self
+            .saved
+            .lock()
+            .expect("nothing panics holding the receiver");
+        match saved.try_recv() {
+            Ok(saved) => Some(saved),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(anyhow!("the clip stopped saving"))),
+        }
+    }
+
+    /// Saves a few more frames. Returns where the clip went once it is saved, or why it
+    /// couldn't be.
+    #[cfg(target_arch = "wasm32")]
+    pub fn poll(&self) -> Option<Result<String>> {
+        self.export.borrow_mut().step(FRAMES_PER_UPDATE).transpose()
+    }
+}
+
+/// Writes a file next to the game.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn save_file(name: &str, bytes: Vec<u8>) -> Result<()> {
+    std::fs::write(name, bytes)?;
+    Ok(())
+}
+
+/// Hands a file to the browser to download.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn save_file(name: &str, bytes: Vec<u8>) -> Result<()> {
+    use eframe::wasm_bindgen::closure::Closure;
+    use eframe::wasm_bindgen::JsCast as _;
+
+    let fail = |err| anyhow!("couldn't download {name}: {err:?}");
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| anyhow!("no document to download {name} from"))?;
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes.as_slice()));
+    let blob = web_sys::Blob::new_with_u8_array_sequence(&parts).map_err(fail)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(fail)?;
+
+    let link = document
+        .create_element("a")
+        .map_err(fail)?
+        .dyn_into::<web_sys::HtmlAnchorElement>()
+        .map_err(|_| anyhow!("couldn't make a link to download {name}"))?;
+    link.set_href(&url);
+    link.set_download(name);
+    link.click();
+    // the download only starts once this returns to the browser, so the file is let go after
+    let revoke = Closure::once_into_js(move || {
+        let _ = web_sys::Url::revoke_object_url(&url);
+    });
+    web_sys::window()
+        .ok_or_else(|| anyhow!("no window to download {name} from"))?
+        .set_timeout_with_callback_and_timeout_and_arguments_0(revoke.unchecked_ref(), 0)
+        .map_err(fail)?;
+    Ok(())
+}
@@ -0,0 +1,160 @@
+//! # controller
+//! Who, or what, presses the buttons.
+//!
+//! Each tick the game asks every player's [`Controller`] which buttons it holds. A person
+//! playing is just the [`Keyboard`] controller, so bots plug in the same way: implement
+//! [`Controller`] and hand it to [`DinoGame::set_controller`](crate::DinoGame::set_controller).
+
+use crate::app::{HITBOX_BACK, HITBOX_FRONT};
+use crate::input::{Input, KeyBindings};
+use crate::run::Run;
+use crate::spawner::JumpArc;
+use egui::Ui;
+
+/// What a controller can see when deciding which buttons to hold.
+pub struct View<'a> {
+    /// The run being controlled, as it was at the end of the last tick.
+    pub run: &'a Run,
+
+    /// This frame's egui input, when there is a window.
+    pub ui: Option<&'a Ui>,
+}
+
+/// Decides a player's buttons each tick.
+///
+/// Controllers have to be [`Clone`] so the game holding them can be cloned too.
+pub trait Controller: ControllerClone {
+    /// The buttons to hold for the next tick.
+    fn act(&mut self, view: &View<'_>) -> Input;
+
+    /// A short name for menus and logs.
+    fn name(&self) -> &str;
+
+    /// Whether a person is pressing the buttons, rather than a bot.
+    fn is_human(&self) -> bool {
+        false
+    }
+}
+
+/// Lets boxed controllers be cloned. This is implemented for every controller that is
+/// [`Clone`].
+pub trait ControllerClone {
+    fn clone_box(&self) -> Box<dyn Controller>;
+}
+
+impl<T: Controller + Clone + 'static> ControllerClone for T {
+    fn clone_box(&self) -> Box<dyn Controller> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Controller> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// A person playing with the keyboard, pointer or a gamepad.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Keyboard {
+    /// Which player this is in a two player race, or `None` to read every gamepad.
+    pub player: Option<usize>,
+
+    /// The keys this person presses.
+    pub keys: KeyBindings,
+}
+
+impl Keyboard {
+    /// Reads `keys`, the pointer and every gamepad.
+    pub fn new(keys: KeyBindings) -> Self {
+        Self { player: None, keys }
+    }
+
+    /// Reads the keys of one player in a two player race, with their own gamepad.
+    pub fn player(player: usize, keys: KeyBindings) -> Self {
+        Self {
+            player: Some(player),
+            keys,
+        }
+    }
+}
+
+impl Controller for Keyboard {
+    fn act(&mut self, view: &View<'_>) -> Input {
+        let Some(ui) = view.ui else {
+            return Input::default();
+        };
+        match self.player {
+            Some(player) => Input::read_player(ui, &self.keys, player),
+            None => Input::read(ui, &self.keys),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Keyboard"
+    }
+
+    fn is_human(&self) -> bool {
+        true
+    }
+}
+
+/// A rule-based bot that times full jumps from where the next obstacle is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Autopilot {
+    arc: JumpArc,
+}
+
+impl Default for Autopilot {
+    fn default() -> Self {
+        Self {
+            arc: JumpArc::full(),
+        }
+    }
+}
+
+impl Autopilot {
+    /// Whether jumping now centres the jump's clear ticks over the ticks the next obstacle
+    /// spends under the dino.
+    fn should_jump(&self, run: &Run) -> bool {
+        let speed = run.distance_per_tick();
+        let next = run
+            .enemies()
+            .filter(|enemy| enemy.end_x > HITBOX_BACK)
+            .min_by(|a, b| a.start_x.total_cmp(&b.start_x));
+        let Some(next) = next else {
+            return false;
+        };
+        let arrival = (next.start_x - HITBOX_FRONT) / speed;
+        let overlap = (next.end_x - next.start_x + HITBOX_FRONT - HITBOX_BACK) / speed;
+        let slack = (self.arc.clear_ticks as f64 - overlap).max(0.0) / 2.0;
+        arrival - self.arc.rise_ticks as f64 <= slack
+    }
+
+    /// Whether nothing is under the dino or about to be, so it can drop back down early.
+    fn can_drop(&self, run: &Run) -> bool {
+        let speed = run.distance_per_tick();
+        run.enemies()
+            .all(|enemy| enemy.end_x <= HITBOX_BACK || enemy.start_x - HITBOX_FRONT > speed * 4.0)
+    }
+}
+
+impl Controller for Autopilot {
+    fn act(&mut self, view: &View<'_>) -> Input {
+        let run = view.run;
+        if run.on_ground() {
+            return Input {
+                jump: self.should_jump(run),
+                duck: false,
+            };
+        }
+        Input {
+            jump: run.rising(),
+            duck: !run.rising() && self.can_drop(run),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Autopilot"
+    }
+}
@@ -0,0 +1,77 @@
+//! # death
+//! What happens on screen after the dino crashes.
+//!
+//! Everything freezes for a moment with the screen shaking, then "GAME OVER" and the restart
+//! icon fade in over the play field. Jumping doesn't restart straight away, so a jump pressed
+//! just too late to save the dino doesn't also throw away the death screen.
+
+use epaint::{vec2, Vec2};
+
+/// How many ticks everything is frozen for before the game over panel appears.
+pub const FREEZE_TICKS: u32 = 12;
+
+/// How many ticks the game over panel takes to fade in.
+pub const PANEL_FADE_TICKS: u32 = 10;
+
+/// How many ticks the screen shakes for.
+pub const SHAKE_TICKS: u32 = 18;
+
+/// How far the screen moves at the start of the shake, in points.
+pub const SHAKE_DISTANCE: f32 = 8.0;
+
+/// How many ticks jumping is ignored for before it restarts the game.
+pub const RESTART_LOCKOUT_TICKS: u32 = 40;
+
+/// How far through the death sequence the game is.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DeathSequence {
+    /// Ticks since the dino crashed.
+    ticks: u32,
+}
+
+impl DeathSequence {
+    /// Moves the sequence on by a tick.
+    pub fn tick(&mut self) {
+        self.ticks = self.ticks.saturating_add(1);
+    }
+
+    pub fn ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    /// Whether the sequence still needs ticking, so the window should keep repainting. That
+    /// lasts until jumping restarts, as ticks only move on when the window repaints.
+    pub fn is_playing(&self) -> bool {
+        self.ticks
+            < (FREEZE_TICKS + PANEL_FADE_TICKS)
+                .max(SHAKE_TICKS)
+                .max(RESTART_LOCKOUT_TICKS)
+    }
+
+    /// Whether everything is still frozen, before the game over panel shows.
+    pub fn is_frozen(&self) -> bool {
+        self.ticks < FREEZE_TICKS
+    }
+
+    /// How far to move the screen this tick. The shake dies away, and is the same every time
+    /// so screenshots can be compared.
+    pub fn shake(&self) -> Vec2 {
+        if self.ticks >= SHAKE_TICKS {
+            return Vec2::ZERO;
+        }
+        let strength = SHAKE_DISTANCE * (1.0 - self.ticks as f32 / SHAKE_TICKS as f32);
+        let t = self.ticks as f32;
+        vec2((t * 2.3).sin(), (t * 3.7).cos()) * strength
+    }
+
+    /// How opaque the game over panel is, from 0 to 1.
+    pub fn panel(&self) -> f32 {
+        let shown = self.ticks.saturating_sub(FREEZE_TICKS);
+        (shown as f32 / PANEL_FADE_TICKS as f32).min(1.0)
+    }
+
+    /// Whether jumping restarts the game yet.
+    pub fn accepts_restart(&self) -> bool {
+        self.ticks >= RESTART_LOCKOUT_TICKS
+    }
+}
@@ -0,0 +1,197 @@
+//! # env
+//! A gym-style environment for training bots, with no window needed.
+//!
+//! ```
+//! use dino::env::{Action, Env};
+//!
+//! let mut env = Env::default().with_max_ticks(1000);
+//! let mut observation = env.reset(7);
+//! loop {
+//!     let action = if observation.features[3] < 200.0 { Action::Jump } else { Action::Nothing };
+//!     let (next, _reward, done) = env.step(action).unwrap();
+//!     if done {
+//!         break;
+//!     }
+//!     observation = next;
+//! }
+//! ```
+
+use crate::app::{GROUND_Y, HITBOX_BACK, HITBOX_FRONT};
+use crate::input::Input;
+use crate::mode::GameMode;
+use crate::raster::Raster;
+use crate::run::Run;
+use crate::settings::Settings;
+use anyhow::Result;
+use image::RgbaImage;
+
+/// How many numbers are in [`Observation::features`].
+pub const FEATURES: usize = 9;
+
+/// How far away an obstacle that isn't there is reported to be.
+pub const NO_OBSTACLE: f32 = 2000.0;
+
+/// What the bot does for one tick.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    #[default]
+    Nothing,
+
+    /// Hold the jump button.
+    Jump,
+
+    /// Hold the duck button, which falls faster in the air.
+    Duck,
+}
+
+impl Action {
+    pub const ALL: [Self; 3] = [Self::Nothing, Self::Jump, Self::Duck];
+}
+
+impl From<Action> for Input {
+    fn from(action: Action) -> Self {
+        Input {
+            jump: action == Action::Jump,
+            duck: action == Action::Duck,
+        }
+    }
+}
+
+impl From<Input> for Action {
+    /// Jumping wins when both buttons are held.
+    fn from(input: Input) -> Self {
+        if input.jump {
+            Self::Jump
+        } else if input.duck {
+            Self::Duck
+        } else {
+            Self::Nothing
+        }
+    }
+}
+
+/// What the bot sees after each tick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    /// In order:
+    /// - how high the dino is above the floor
+    /// - how fast it is moving upwards
+    /// - how far everything scrolls each tick
+    /// - for each of the next two obstacles, its distance to the front of the dino, its width
+    ///   and how high the dino has to be to clear it, or [`NO_OBSTACLE`] and zeros when there
+    ///   isn't one
+    pub features: [f32; FEATURES],
+
+    /// The field drawn on the CPU, when the environment was made with
+    /// [`Env::with_pixels`].
+    pub pixels: Option<RgbaImage>,
+}
+
+/// One dino's run in classic mode, stepped a tick at a time.
+#[derive(Clone)]
+pub struct Env {
+    run: Run,
+    settings: Settings,
+    raster: Option<Raster>,
+
+    /// Steps taken since the last reset.
+    ticks: u32,
+
+    /// The run ends after this many ticks even if the dino is still alive.
+    max_ticks: Option<u32>,
+}
+
+impl Default for Env {
+    fn default() -> Self {
+        Self {
+            run: Run::new(0),
+            settings: Settings::default(),
+            raster: None,
+            ticks: 0,
+            max_ticks: None,
+        }
+    }
+}
+
+impl Env {
+    /// Also draws every observation, with `scale` pixels for each game unit.
+    pub fn with_pixels(mut self, scale: f32) -> Self {
+        self.raster = Some(Raster::new(scale));
+        self
+    }
+
+    /// Ends runs after `max_ticks` steps.
+    pub fn with_max_ticks(mut self, max_ticks: u32) -> Self {
+        self.max_ticks = Some(max_ticks);
+        self
+    }
+
+    /// Plays with `settings` instead of the defaults.
+    pub fn with_settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Starts a new run whose obstacles are determined by `seed`.
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.run = Run::new(seed);
+        self.ticks = 0;
+        // the first jump is what starts a run
+        let _ = self.run.jump();
+        self.observe()
+    }
+
+    /// Advances the run by one tick. The reward is the number of points scored during the tick.
+    pub fn step(&mut self, action: Action) -> Result<(Observation, f64, bool)> {
+        let before = self.run.score();
+        self.ticks += 1;
+        self.run
+            .step(action.into(), &self.settings, GameMode::Classic)?;
+        let reward = (self.run.score() - before) / 85.0;
+        Ok((self.observe(), reward, self.is_done()))
+    }
+
+    /// Whether the run is over and needs a [`Env::reset`].
+    pub fn is_done(&self) -> bool {
+        self.run.is_finished()
+            || self
+                .max_ticks
+                .is_some_and(|max_ticks| self.ticks >= max_ticks)
+    }
+
+    /// The run being played, for bots that want more than the observation.
+    pub fn run(&self) -> &Run {
+        &self.run
+    }
+
+    /// What the bot sees right now.
+    pub fn observe(&mut self) -> Observation {
+        let run = &self.run;
+        let mut ahead: Vec<_> = run
+            .enemies()
+            .filter(|enemy| enemy.end_x > HITBOX_BACK)
+            .collect();
+        ahead.sort_by(|a, b| a.start_x.total_cmp(&b.start_x));
+
+        let mut features = [0.0; FEATURES];
+        features[0] = (GROUND_Y - run.dino_y()) as f32;
+        features[1] = -run.dino_speed_y as f32;
+        features[2] = run.distance_per_tick() as f32;
+        for (slot, chunk) in features[3..].chunks_mut(3).enumerate() {
+            match ahead.get(slot) {
+                Some(enemy) => {
+                    chunk[0] = (enemy.start_x - HITBOX_FRONT) as f32;
+                    chunk[1] = (enemy.start_x - enemy.end_x).abs() as f32;
+                    chunk[2] = enemy.hitbox_height() as f32;
+                }
+                None => chunk[0] = NO_OBSTACLE,
+            }
+        }
+
+        let pixels = self.raster.as_mut().map(|raster| {
+            raster.draw_run(run);
+            raster.image().clone()
+        });
+        Observation { features, pixels }
+    }
+}
@@ -0,0 +1,165 @@
+//! # input
+//! Turns egui's input into the buttons the game cares about.
+//!
+//! The game needs to know how long buttons are held for, not just when they are pressed, so the
+//! state of every button is read once per tick and compared with the previous tick.
+//!
+//! Which keys press which buttons is part of the [settings](crate::settings), so each profile
+//! keeps its own. In a two player race the keyboard is split in half: by default the first
+//! player uses W, S and space, the second the arrow keys. In the browser each player can also use
+//! a gamepad.
+
+use egui::{InputState, Key, Pos2, Ui};
+
+/// How many gamepads are read, one for each player in a two player race.
+const GAMEPADS: usize = 2;
+
+/// The keys that press the game's buttons.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct KeyBindings {
+    pub jump: Vec<Key>,
+    pub duck: Vec<Key>,
+}
+
+impl KeyBindings {
+    /// The keys for playing alone: W, up or space jump, and S or down ducks.
+    pub fn single() -> Self {
+        Self {
+            jump: vec![Key::W, Key::ArrowUp, Key::Space],
+            duck: vec![Key::S, Key::ArrowDown],
+        }
+    }
+
+    /// The keys each player uses in a two player race.
+    pub fn split() -> [Self; 2] {
+        [
+            Self {
+                jump: vec![Key::W, Key::Space],
+                duck: vec![Key::S],
+            },
+            Self {
+                jump: vec![Key::ArrowUp],
+                duck: vec![Key::ArrowDown],
+            },
+        ]
+    }
+
+    fn read(&self, input: &InputState) -> Input {
+        Input {
+            jump: self.jump.iter().any(|key| held(input, *key)),
+            duck: self.duck.iter().any(|key| held(input, *key)),
+        }
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::single()
+    }
+}
+
+/// Which of the game's buttons are held down during a tick.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Input {
+    pub jump: bool,
+    pub duck: bool,
+}
+
+impl Input {
+    /// Reads the buttons from egui, pressed with `keys`, the pointer or any gamepad.
+    ///
+    /// A key that was both pressed and released within one frame still counts as held for that
+    /// tick, so quick taps are not lost.
+    pub fn read(ui: &Ui, keys: &KeyBindings) -> Self {
+        let input = ui.input(|i| {
+            let mut input = keys.read(i);
+            input.jump |= pointer_held(i);
+            input
+        });
+        (0..GAMEPADS).fold(input, |input, index| input.or(gamepad(index)))
+    }
+
+    /// Reads the buttons of one player in a two player race, pressed with `keys` or their own
+    /// gamepad. The pointer belongs to the first player.
+    pub fn read_player(ui: &Ui, keys: &KeyBindings, player: usize) -> Self {
+        let input = ui.input(|i| {
+            let mut input = keys.read(i);
+            input.jump |= player == 0 && pointer_held(i);
+            input
+        });
+        input.or(gamepad(player))
+    }
+
+    /// Packs the buttons into two bits, jump then duck, for sending or saving.
+    pub fn to_bits(self) -> u8 {
+        u8::from(self.jump) | (u8::from(self.duck) << 1)
+    }
+
+    /// Unpacks buttons packed by [`Input::to_bits`].
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            jump: bits & 1 != 0,
+            duck: bits & 2 != 0,
+        }
+    }
+
+    fn or(self, other: Self) -> Self {
+        Self {
+            jump: self.jump || other.jump,
+            duck: self.duck || other.duck,
+        }
+    }
+}
+
+fn held(input: &InputState, key: Key) -> bool {
+    input.key_down(key) || input.key_pressed(key)
+}
+
+/// Holding the pointer down inside the play area counts as holding jump.
+fn pointer_held(input: &InputState) -> bool {
+    let pointer = &input.pointer;
+    (pointer.primary_down() || pointer.primary_pressed())
+        && pointer.press_origin().is_some_and(in_play_area)
+}
+
+/// Whether a position is inside the area the game is drawn in.
+fn in_play_area(pos: Pos2) -> bool {
+    (25.0..=1502.0).contains(&pos.x) && (108.0..=327.0).contains(&pos.y)
+}
+
+/// Reads the `index`th connected gamepad, using the browser's standard button layout: A or up on
+/// the d-pad jumps, down on the d-pad or the left stick ducks.
+#[cfg(target_arch = "wasm32")]
+fn gamepad(index: usize) -> Input {
+    use eframe::wasm_bindgen::JsCast as _;
+
+    let gamepad = web_sys::window()
+        .and_then(|window| window.navigator().get_gamepads().ok())
+        .and_then(|gamepads| {
+            gamepads
+                .get(index as u32)
+                .dyn_into::<web_sys::Gamepad>()
+                .ok()
+        });
+    let Some(gamepad) = gamepad else {
+        return Input::default();
+    };
+    let buttons = gamepad.buttons();
+    let pressed = |button: u32| {
+        buttons
+            .get(button)
+            .dyn_into::<web_sys::GamepadButton>()
+            .is_ok_and(|button| button.pressed())
+    };
+    let stick_down = gamepad.axes().get(1).as_f64().is_some_and(|y| y > 0.5);
+    Input {
+        jump: pressed(0) || pressed(12),
+        duck: pressed(13) || stick_down,
+    }
+}
+
+/// Gamepads are only read in the browser for now.
+#[cfg(not(target_arch = "wasm32"))]
+fn gamepad(_index: usize) -> Input {
+    Input::default()
+}
@@ -0,0 +1,92 @@
+//! # intro
+//! What happens on screen before a run gets going.
+//!
+//! The dino waits, blinking, on a screen just wide enough for it. The first jump wipes the
+//! rest of the play field into view, then the floor and the score fade in. The intro only
+//! changes what is drawn, so skipping it never changes how a run plays.
+
+/// How many ticks the wipe takes to show the whole play field.
+pub const REVEAL_TICKS: u32 = 30;
+
+/// How many ticks the floor and score take to fade in once the wipe is done.
+pub const FADE_TICKS: u32 = 20;
+
+/// How far through the intro a run is.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IntroPhase {
+    /// Waiting for the first jump, with only the dino showing.
+    #[default]
+    Idle,
+
+    /// The play field is being wiped into view.
+    Reveal,
+
+    /// The floor and score are fading in.
+    FadeIn,
+
+    /// Everything is showing.
+    Done,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Intro {
+    phase: IntroPhase,
+
+    /// Ticks spent in the current phase.
+    ticks: u32,
+}
+
+impl Intro {
+    pub fn phase(&self) -> IntroPhase {
+        self.phase
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.phase == IntroPhase::Done
+    }
+
+    /// Moves the intro on by a tick. It stays idle until `started` is set by the first jump.
+    pub fn tick(&mut self, started: bool) {
+        let (next, length) = match self.phase {
+            IntroPhase::Idle if started => (IntroPhase::Reveal, 0),
+            IntroPhase::Idle | IntroPhase::Done => return,
+            IntroPhase::Reveal => (IntroPhase::FadeIn, REVEAL_TICKS),
+            IntroPhase::FadeIn => (IntroPhase::Done, FADE_TICKS),
+        };
+        self.ticks += 1;
+        if self.ticks >= length {
+            self.phase = next;
+            self.ticks = 0;
+        }
+    }
+
+    /// Jumps to the end of the intro.
+    pub fn skip(&mut self) {
+        self.phase = IntroPhase::Done;
+        self.ticks = 0;
+    }
+
+    /// How much of the play field is showing, from 0 to 1. The wipe eases in and out.
+    pub fn reveal(&self) -> f32 {
+        match self.phase {
+            IntroPhase::Idle => 0.0,
+            IntroPhase::Reveal => smoothstep(self.ticks as f32 / REVEAL_TICKS as f32),
+            IntroPhase::FadeIn | IntroPhase::Done => 1.0,
+        }
+    }
+
+    /// How opaque the floor and score are, from 0 to 1.
+    pub fn fade(&self) -> f32 {
+        match self.phase {
+            IntroPhase::Idle | IntroPhase::Reveal => 0.0,
+            IntroPhase::FadeIn => self.ticks as f32 / FADE_TICKS as f32,
+            IntroPhase::Done => 1.0,
+        }
+    }
+}
+
+/// Eases `t` from 0 to 1, starting and stopping gently.
+fn smoothstep(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
@@ -1,5 +1,30 @@
 #![warn(clippy::all, rust_2018_idioms)]
 #![allow(clippy::too_many_arguments)]
+pub mod animation;
 mod app;
+pub mod assets;
+pub mod assist;
+pub mod batch;
+pub mod clip;
+pub mod controller;
+pub mod death;
+pub mod env;
+pub mod input;
+pub mod intro;
+pub mod locale;
+pub mod mode;
+pub mod narration;
+pub mod net;
+pub mod particles;
+pub mod powerup;
+pub mod profile;
+pub mod raster;
 pub mod render;
-pub use app::DinoGame;
+pub mod replay;
+pub mod run;
+pub mod save;
+pub mod settings;
+pub mod spawner;
+pub mod sprite;
+pub mod theme;
+pub use app::{DinoGame, Enemy};
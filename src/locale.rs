@@ -0,0 +1,263 @@
+//! # locale
+//! The text the game shows, in each language it ships with.
+//!
+//! Every string lives in a [Fluent](https://projectfluent.org) file in `assets/locales`, one per
+//! language, under a message id shared by all of them. A [`Locale`] looks messages up in one
+//! language and falls back to English for any it is missing.
+//!
+//! egui lays text out left to right and has no fonts for Hebrew, so right-to-left languages get
+//! their messages put in display order here, a fallback font with their letters, and menus that
+//! line up from the right.
+
+use egui::{Align, FontData, FontDefinitions, FontFamily, Layout, Ui};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use log::warn;
+use std::sync::{Arc, OnceLock};
+use unic_langid::{CharacterDirection, LanguageIdentifier};
+
+/// The language everything is written in first, which the others fall back to.
+const FALLBACK: Language = Language::English;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    German,
+    Hebrew,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::Spanish,
+        Language::German,
+        Language::Hebrew,
+    ];
+
+    /// The language's name in that language, so it can be found whatever the game is showing.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+            Language::German => "Deutsch",
+            Language::Hebrew => "עברית",
+        }
+    }
+
+    /// [`Self::name`], ready to draw.
+    pub fn label(self) -> String {
+        if self.is_rtl() {
+            display_order(self.name())
+        } else {
+            self.name().to_owned()
+        }
+    }
+
+    /// The language's BCP 47 tag.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::German => "de",
+            Language::Hebrew => "he",
+        }
+    }
+
+    /// The Fluent file with every message in this language.
+    pub fn source(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../assets/locales/en.ftl"),
+            Language::Spanish => include_str!("../assets/locales/es.ftl"),
+            Language::German => include_str!("../assets/locales/de.ftl"),
+            Language::Hebrew => include_str!("../assets/locales/he.ftl"),
+        }
+    }
+
+    fn id(self) -> LanguageIdentifier {
+        self.tag().parse().unwrap_or_default()
+    }
+
+    /// Whether the language is written right to left.
+    pub fn is_rtl(self) -> bool {
+        self.id().character_direction() == CharacterDirection::RTL
+    }
+
+    /// The shipped language that best matches a locale such as `de-AT`, or English if none do.
+    pub fn negotiate(locale: &str) -> Language {
+        let Ok(requested) = locale.parse::<LanguageIdentifier>() else {
+            return FALLBACK;
+        };
+        let available = Self::ALL.map(Self::id);
+        let default = FALLBACK.id();
+        let supported = fluent_langneg::negotiate_languages(
+            &[requested],
+            &available,
+            Some(&default),
+            fluent_langneg::NegotiationStrategy::Lookup,
+        );
+        supported
+            .first()
+            .and_then(|id| Self::ALL.into_iter().find(|language| language.id() == **id))
+            .unwrap_or(FALLBACK)
+    }
+
+    /// The shipped language that best matches the system's or browser's locale. It is only
+    /// looked up once, as the settings screen asks for it every frame.
+    pub fn detect() -> Language {
+        static DETECTED: OnceLock<Language> = OnceLock::new();
+        *DETECTED.get_or_init(|| {
+            sys_locale::get_locale().map_or(FALLBACK, |locale| Self::negotiate(&locale))
+        })
+    }
+}
+
+/// Looks up messages in one language.
+#[derive(Clone)]
+pub struct Locale {
+    language: Language,
+    bundle: Arc<FluentBundle<FluentResource>>,
+
+    /// English, for messages the language is missing.
+    fallback: Option<Arc<FluentBundle<FluentResource>>>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::new(FALLBACK)
+    }
+}
+
+impl Locale {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            bundle: Arc::new(bundle(language)),
+            fallback: (language != FALLBACK).then(|| Arc::new(bundle(FALLBACK))),
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Whether the language itself has the message `id`, without falling back to English.
+    pub fn has(&self, id: &str) -> bool {
+        self.bundle.has_message(id)
+    }
+
+    /// The message `id`, or `id` itself if no language has it.
+    pub fn tr(&self, id: &str) -> String {
+        self.tr_with(id, &[])
+    }
+
+    /// The message `id` with its variables filled in from `args`.
+    pub fn tr_with(&self, id: &str, args: &[(&str, FluentValue<'_>)]) -> String {
+        let text = self.speak_with(id, args);
+        if self.is_rtl() {
+            display_order(&text)
+        } else {
+            text
+        }
+    }
+
+    /// Like [`Self::tr_with`], but in reading order for screen readers rather than put in order
+    /// for display.
+    pub fn speak_with(&self, id: &str, args: &[(&str, FluentValue<'_>)]) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        let found = std::iter::once(&self.bundle)
+            .chain(&self.fallback)
+            .find_map(|bundle| Some((bundle, bundle.get_message(id)?.value()?)));
+        let Some((bundle, pattern)) = found else {
+            warn!("no message {id} in {}", self.language.tag());
+            return id.to_owned();
+        };
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+        if !errors.is_empty() {
+            warn!("couldn't format {id}: {errors:?}");
+        }
+        text.into_owned()
+    }
+
+    /// Whether the language is written right to left.
+    pub fn is_rtl(&self) -> bool {
+        self.language.is_rtl()
+    }
+
+    /// A layout for menus that lines them up on the side the language starts from.
+    pub fn layout(&self) -> Layout {
+        Layout::top_down(if self.is_rtl() {
+            Align::Max
+        } else {
+            Align::Min
+        })
+    }
+
+    /// Lays out a row of widgets in reading order.
+    pub fn row<R>(&self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        if self.is_rtl() {
+            ui.with_layout(Layout::right_to_left(Align::Center), add_contents)
+                .inner
+        } else {
+            ui.horizontal(add_contents).inner
+        }
+    }
+}
+
+/// Parses a shipped Fluent file into a bundle, keeping whatever parsed if some of it didn't.
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let resource = FluentResource::try_new(language.source().to_owned()).unwrap_or_else(
+        |(resource, errors)| {
+            warn!("errors in {}.ftl: {errors:?}", language.tag());
+            resource
+        },
+    );
+    let mut bundle = FluentBundle::new_concurrent(vec![language.id()]);
+    // egui has no glyphs for the isolation marks, and text is reordered for display anyway
+    bundle.set_use_isolating(false);
+    if let Err(errors) = bundle.add_resource(resource) {
+        warn!("errors in {}.ftl: {errors:?}", language.tag());
+    }
+    bundle
+}
+
+/// Reorders right-to-left text so that drawing it left to right shows it the right way round.
+fn display_order(text: &str) -> String {
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| {
+            let bidi = unicode_bidi::BidiInfo::new(line, Some(unicode_bidi::Level::rtl()));
+            bidi.paragraphs
+                .first()
+                .map_or_else(String::new, |paragraph| {
+                    bidi.reorder_line(paragraph, paragraph.range.clone())
+                        .into_owned()
+                })
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Adds a fallback font with the letters egui's own fonts are missing, such as Hebrew.
+pub fn install_fonts(ctx: &egui::Context) {
+    let mut fonts = FontDefinitions::default();
+    fonts.font_data.insert(
+        "DejaVuSans".to_owned(),
+        Arc::new(FontData::from_static(include_bytes!(
+            "../assets/fonts/DejaVuSans-Hebrew.ttf"
+        ))),
+    );
+    for family in [FontFamily::Proportional, FontFamily::Monospace] {
+        fonts
+            .families
+            .entry(family)
+            .or_default()
+            .push("DejaVuSans".to_owned());
+    }
+    ctx.set_fonts(fonts);
+}
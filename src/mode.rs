@@ -0,0 +1,175 @@
+//! # mode
+//! The different ways the game can be played, picked from the main menu.
+
+use web_time::{SystemTime, UNIX_EPOCH};
+
+/// How long a time attack run lasts for, in ticks. The game ticks once a frame, at 60 frames a
+/// second.
+pub const TIME_ATTACK_TICKS: u32 = 60 * 60;
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub enum GameMode {
+    /// The original game.
+    #[default]
+    Classic,
+
+    /// Obstacles can't kill the dino, the run lasts until the player stops it.
+    Zen,
+
+    /// Go as far as possible in 60 seconds.
+    TimeAttack,
+
+    /// The same obstacles for everyone on a given day.
+    Daily,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 4] = [
+        GameMode::Classic,
+        GameMode::Zen,
+        GameMode::TimeAttack,
+        GameMode::Daily,
+    ];
+
+    /// The id of the mode's name in the [`Locale`](crate::locale::Locale).
+    pub fn message_id(self) -> &'static str {
+        match self {
+            GameMode::Classic => "mode-classic",
+            GameMode::Zen => "mode-zen",
+            GameMode::TimeAttack => "mode-time-attack",
+            GameMode::Daily => "mode-daily",
+        }
+    }
+
+    /// The id of the mode's description in the [`Locale`](crate::locale::Locale).
+    pub fn description_id(self) -> &'static str {
+        match self {
+            GameMode::Classic => "mode-classic-description",
+            GameMode::Zen => "mode-zen-description",
+            GameMode::TimeAttack => "mode-time-attack-description",
+            GameMode::Daily => "mode-daily-description",
+        }
+    }
+
+    /// Whether hitting an obstacle ends the run.
+    pub fn can_die(self) -> bool {
+        self != GameMode::Zen
+    }
+
+    /// Picks the seed for a new run's obstacles.
+    pub fn seed(self) -> u64 {
+        match self {
+            GameMode::Daily => daily_seed(today()),
+            _ => rand::random(),
+        }
+    }
+}
+
+/// The current day, counted in days since 1970-01-01 in UTC.
+pub fn today() -> i64 {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    (seconds / (24 * 60 * 60)) as i64
+}
+
+/// The seed everyone plays the daily challenge with on `day`.
+pub fn daily_seed(day: i64) -> u64 {
+    (day as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+}
+
+/// Formats a day from [`today`] as `yyyy-mm-dd`.
+pub fn format_day(day: i64) -> String {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = day + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+/// The score of an official daily challenge attempt.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct DailyResult {
+    pub day: i64,
+    pub score: f64,
+}
+
+/// Every official daily challenge attempt, kept apart from the other modes' scores.
+#[derive(Debug, Default, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct DailyRecord {
+    pub results: Vec<DailyResult>,
+}
+
+impl DailyRecord {
+    /// The official score for `day`, if it has been played.
+    pub fn result(&self, day: i64) -> Option<f64> {
+        self.results
+            .iter()
+            .find(|result| result.day == day)
+            .map(|result| result.score)
+    }
+
+    /// Records a run starting on `day`, returning whether it is the day's official attempt.
+    /// The attempt scores 0 until it [finishes](Self::finish), so a run that is quit or closed
+    /// part way still uses up the day.
+    pub fn start(&mut self, day: i64) -> bool {
+        if self.result(day).is_some() {
+            return false;
+        }
+        self.results.push(DailyResult { day, score: 0.0 });
+        true
+    }
+
+    /// Records the score of `day`'s official attempt once it ends.
+    pub fn finish(&mut self, day: i64, score: f64) {
+        if let Some(result) = self.results.iter_mut().find(|result| result.day == day) {
+            result.score = score;
+        }
+    }
+
+    /// The best official attempts, best first.
+    pub fn leaderboard(&self) -> Vec<DailyResult> {
+        let mut results = self.results.clone();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results
+    }
+}
+
+/// How many finished runs the history keeps.
+pub const HISTORY_LENGTH: usize = 20;
+
+/// A finished one player run.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct RunResult {
+    pub mode: GameMode,
+    pub score: f64,
+
+    /// Whether any assist was on, which keeps the score apart from the best scores.
+    pub assisted: bool,
+}
+
+/// The most recent finished runs, oldest first.
+#[derive(Debug, Default, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct RunHistory {
+    pub runs: Vec<RunResult>,
+}
+
+impl RunHistory {
+    /// Adds a finished run, forgetting the oldest once there are too many.
+    pub fn record(&mut self, result: RunResult) {
+        self.runs.push(result);
+        if self.runs.len() > HISTORY_LENGTH {
+            self.runs.remove(0);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! # narration
+//! What a screen reader says about a run.
+//!
+//! The play field is drawn with a bare painter, so a screen reader can't see anything in it.
+//! A [`Narrator`] watches the run instead and writes short announcements, which the game puts
+//! in a live region on the play field for the screen reader to read out.
+
+use crate::app::HITBOX_FRONT;
+use crate::locale::Locale;
+use crate::run::Run;
+
+/// How many points apart score milestones are.
+pub const MILESTONE: u32 = 100;
+
+/// Turns the score shown on screen into whole points.
+fn points(score: f64) -> u32 {
+    (score / 85.0) as u32
+}
+
+/// Watches a run and says what is worth saying about it.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Narrator {
+    /// The best score before this run, so beating it is only announced once.
+    best: f64,
+    beat_best: bool,
+
+    /// The last milestone announced.
+    milestone: u32,
+
+    /// Where the far end of the obstacle announced last was, to tell when a new one is next.
+    next_obstacle: Option<f64>,
+    finished: bool,
+
+    /// Whether to announce each obstacle as it becomes the next one.
+    obstacles: bool,
+}
+
+impl Narrator {
+    /// A narrator for a run that beats the high score if it gets past `best`. Obstacles are
+    /// announced too when `obstacles` is set.
+    pub fn new(best: f64, obstacles: bool) -> Self {
+        Self {
+            best,
+            beat_best: false,
+            milestone: 0,
+            next_obstacle: None,
+            finished: false,
+            obstacles,
+        }
+    }
+
+    /// What to announce about the run since the last time this was called, most urgent last,
+    /// in `locale`'s language.
+    pub fn update(&mut self, run: &Run, locale: &Locale) -> Vec<String> {
+        let mut said = Vec::new();
+        let score = points(run.score());
+
+        if self.finished {
+            return said;
+        }
+        if run.is_finished() {
+            self.finished = true;
+            said.push(locale.speak_with("narration-game-over", &[("score", score.into())]));
+            return said;
+        }
+
+        let milestone = score / MILESTONE * MILESTONE;
+        if milestone > self.milestone {
+            self.milestone = milestone;
+            said.push(locale.speak_with("narration-milestone", &[("score", milestone.into())]));
+        }
+
+        if !self.beat_best && self.best > 0.0 && run.score() > self.best {
+            self.beat_best = true;
+            said.push(locale.speak_with("narration-high-score", &[]));
+        }
+
+        if self.obstacles {
+            let next = run
+                .enemies()
+                .filter(|enemy| enemy.start_x > HITBOX_FRONT)
+                .min_by(|a, b| a.start_x.total_cmp(&b.start_x));
+            // the obstacle announced last only gets closer, so one further away is new
+            let is_new = |end_x| {
+                self.next_obstacle
+                    .map_or(true, |announced| announced < end_x)
+            };
+            match next {
+                Some(next) if is_new(next.end_x) => {
+                    self.next_obstacle = Some(next.end_x);
+                    let ticks = (next.start_x - HITBOX_FRONT) / run.distance_per_tick();
+                    let obstacle = locale.speak_with(next.message_id(), &[]);
+                    let seconds = format!("{:.1}", ticks / 60.0);
+                    said.push(locale.speak_with(
+                        "narration-obstacle",
+                        &[("obstacle", obstacle.into()), ("seconds", seconds.into())],
+                    ));
+                }
+                Some(next) => self.next_obstacle = Some(next.end_x),
+                None => self.next_obstacle = None,
+            }
+        }
+        said
+    }
+}
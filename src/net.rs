@@ -0,0 +1,346 @@
+//! # net
+//! Racing other players online.
+//!
+//! Players join a room on a relay server, which picks a seed once the room is full. From then on
+//! the game runs in lockstep: every client simulates every player's run, and only sends the
+//! buttons its own player held on each tick. A tick is only simulated once everyone's buttons for
+//! it have arrived, so each player's input is scheduled a few ticks ahead to hide the latency.
+
+pub mod client;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod relay;
+
+use crate::input::Input;
+use anyhow::{anyhow, Context as _, Result};
+use client::{Connection, Received};
+use std::collections::BTreeMap;
+
+/// How many players race in each room.
+pub const PLAYERS: usize = 2;
+
+/// The relay address filled in on the main menu.
+pub const DEFAULT_RELAY: &str = "ws://127.0.0.1:9001";
+
+/// Why `room` can't be joined, as a message id for the [`Locale`](crate::locale::Locale).
+pub fn room_problem(room: &str) -> Option<&'static str> {
+    room.trim().is_empty().then_some("room-empty")
+}
+
+/// A message sent from a client to the relay.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientMessage {
+    /// Waits in `room` for an opponent.
+    Join { room: String },
+
+    /// The buttons this client's player holds on `tick`.
+    Input { tick: u32, input: Input },
+}
+
+/// A message sent from the relay to a client.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelayMessage {
+    /// The room is full and the race starts with `seed`. The client plays as `player`.
+    Start {
+        seed: u64,
+        player: usize,
+        players: usize,
+    },
+
+    /// The buttons another player holds on `tick`.
+    Input {
+        player: usize,
+        tick: u32,
+        input: Input,
+    },
+
+    /// Another player has disconnected.
+    Left { player: usize },
+}
+
+fn input_from_text(text: &str) -> Result<Input> {
+    Ok(Input::from_bits(text.parse()?))
+}
+
+/// Takes the next word of a message, failing if there is none.
+fn next<'a>(words: &mut impl Iterator<Item = &'a str>) -> Result<&'a str> {
+    words.next().context("message is missing a field")
+}
+
+impl ClientMessage {
+    pub fn to_text(&self) -> String {
+        match self {
+            ClientMessage::Join { room } => format!("join {}", room.replace(' ', "_")),
+            ClientMessage::Input { tick, input } => {
+                format!("input {tick} {}", input.to_bits())
+            }
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut words = text.split_whitespace();
+        match next(&mut words)? {
+            "join" => Ok(ClientMessage::Join {
+                room: next(&mut words)?.to_owned(),
+            }),
+            "input" => Ok(ClientMessage::Input {
+                tick: next(&mut words)?.parse()?,
+                input: input_from_text(next(&mut words)?)?,
+            }),
+            other => Err(anyhow!("unknown message {other}")),
+        }
+    }
+}
+
+impl RelayMessage {
+    pub fn to_text(&self) -> String {
+        match self {
+            RelayMessage::Start {
+                seed,
+                player,
+                players,
+            } => format!("start {seed} {player} {players}"),
+            RelayMessage::Input {
+                player,
+                tick,
+                input,
+            } => format!("input {player} {tick} {}", input.to_bits()),
+            RelayMessage::Left { player } => format!("left {player}"),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut words = text.split_whitespace();
+        match next(&mut words)? {
+            "start" => Ok(RelayMessage::Start {
+                seed: next(&mut words)?.parse()?,
+                player: next(&mut words)?.parse()?,
+                players: next(&mut words)?.parse()?,
+            }),
+            "input" => Ok(RelayMessage::Input {
+                player: next(&mut words)?.parse()?,
+                tick: next(&mut words)?.parse()?,
+                input: input_from_text(next(&mut words)?)?,
+            }),
+            "left" => Ok(RelayMessage::Left {
+                player: next(&mut words)?.parse()?,
+            }),
+            other => Err(anyhow!("unknown message {other}")),
+        }
+    }
+}
+
+/// Collects every player's buttons and hands them out one tick at a time, once all of them are
+/// known.
+#[derive(Debug, Clone)]
+pub struct Lockstep {
+    player: usize,
+
+    /// How many ticks ahead the local player's buttons are scheduled.
+    delay: u32,
+
+    /// The next tick to simulate.
+    next_tick: u32,
+
+    /// The next tick to schedule the local player's buttons for.
+    scheduled: u32,
+
+    inputs: Vec<BTreeMap<u32, Input>>,
+    left: Vec<bool>,
+}
+
+impl Lockstep {
+    pub fn new(player: usize, players: usize, delay: u32) -> Self {
+        Self {
+            player,
+            delay,
+            next_tick: 0,
+            scheduled: 0,
+            inputs: vec![BTreeMap::new(); players],
+            left: vec![false; players],
+        }
+    }
+
+    /// The player this client controls.
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    /// Schedules the local player's buttons `delay` ticks after the next tick, returning every
+    /// tick scheduled so it can be sent to the other players.
+    ///
+    /// The first `delay` ticks are scheduled with nothing held. Nothing is scheduled while the
+    /// other players are too far behind.
+    pub fn schedule(&mut self, input: Input) -> Vec<(u32, Input)> {
+        let mut scheduled = Vec::new();
+        while self.scheduled <= self.next_tick + self.delay {
+            let tick = self.scheduled;
+            let input = if tick < self.delay {
+                Input::default()
+            } else {
+                input
+            };
+            self.inputs[self.player].insert(tick, input);
+            scheduled.push((tick, input));
+            self.scheduled += 1;
+        }
+        scheduled
+    }
+
+    /// Stores another player's buttons.
+    pub fn receive(&mut self, player: usize, tick: u32, input: Input) {
+        if let Some(inputs) = self.inputs.get_mut(player) {
+            inputs.insert(tick, input);
+        }
+    }
+
+    /// Stops waiting for a player who has left. They hold nothing from now on.
+    pub fn leave(&mut self, player: usize) {
+        if let Some(left) = self.left.get_mut(player) {
+            *left = true;
+        }
+    }
+
+    /// Takes every player's buttons for the next tick, if they have all arrived.
+    pub fn advance(&mut self) -> Option<Vec<Input>> {
+        let tick = self.next_tick;
+        let ready = (0..self.inputs.len())
+            .all(|player| self.left[player] || self.inputs[player].contains_key(&tick));
+        if !ready {
+            return None;
+        }
+        self.next_tick += 1;
+        Some(
+            self.inputs
+                .iter_mut()
+                .map(|inputs| inputs.remove(&tick).unwrap_or_default())
+                .collect(),
+        )
+    }
+}
+
+/// Something that happened to an online session.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// The room filled up and the race has started.
+    Start {
+        seed: u64,
+        player: usize,
+        players: usize,
+    },
+
+    /// Another player disconnected.
+    Left { player: usize },
+
+    /// The connection to the relay was lost.
+    Disconnected(String),
+}
+
+/// A connection to a relay, and the lockstep state of the race played over it.
+#[derive(Clone)]
+pub struct Session {
+    connection: Connection,
+    delay: u32,
+    lockstep: Option<Lockstep>,
+}
+
+impl Session {
+    /// Connects to the relay at `address` and waits in `room` for an opponent. The local
+    /// player's buttons will be scheduled `delay` ticks ahead.
+    pub fn connect(address: &str, room: &str, delay: u32) -> Self {
+        let connection = Connection::connect(address);
+        connection.send(
+            ClientMessage::Join {
+                room: room.to_owned(),
+            }
+            .to_text(),
+        );
+        Self {
+            connection,
+            delay,
+            lockstep: None,
+        }
+    }
+
+    /// The player this client controls, once the race has started.
+    pub fn player(&self) -> Option<usize> {
+        self.lockstep.as_ref().map(Lockstep::player)
+    }
+
+    /// Handles everything the relay has sent since the last call.
+    pub fn poll(&mut self) -> Vec<Event> {
+        let mut events = Vec::new();
+        for received in self.connection.receive() {
+            let text = match received {
+                Received::Text(text) => text,
+                Received::Closed(reason) => {
+                    events.push(Event::Disconnected(reason));
+                    continue;
+                }
+            };
+            let message = match RelayMessage::parse(&text) {
+                Ok(message) => message,
+                Err(err) => {
+                    log::warn!("ignoring message {text:?} from the relay: {err}");
+                    continue;
+                }
+            };
+            match message {
+                RelayMessage::Start {
+                    seed,
+                    player,
+                    players,
+                } => {
+                    if player >= players || players > PLAYERS {
+                        // a race that can't be played, so there is no telling what else is wrong
+                        self.connection.close();
+                        events.push(Event::Disconnected(format!(
+                            "the relay started a race as player {} of {players}",
+                            player + 1
+                        )));
+                        return events;
+                    }
+                    self.lockstep = Some(Lockstep::new(player, players, self.delay));
+                    events.push(Event::Start {
+                        seed,
+                        player,
+                        players,
+                    });
+                }
+                RelayMessage::Input {
+                    player,
+                    tick,
+                    input,
+                } => {
+                    if let Some(lockstep) = &mut self.lockstep {
+                        lockstep.receive(player, tick, input);
+                    }
+                }
+                RelayMessage::Left { player } => {
+                    if let Some(lockstep) = &mut self.lockstep {
+                        lockstep.leave(player);
+                    }
+                    events.push(Event::Left { player });
+                }
+            }
+        }
+        events
+    }
+
+    /// Sends the local player's buttons and returns every player's buttons for each tick that
+    /// can now be simulated, in order.
+    pub fn tick(&mut self, input: Input) -> Vec<Vec<Input>> {
+        let Some(lockstep) = &mut self.lockstep else {
+            return Vec::new();
+        };
+        for (tick, input) in lockstep.schedule(input) {
+            self.connection
+                .send(ClientMessage::Input { tick, input }.to_text());
+        }
+        std::iter::from_fn(|| lockstep.advance()).collect()
+    }
+
+    /// Disconnects from the relay.
+    pub fn close(&self) {
+        self.connection.close();
+    }
+}
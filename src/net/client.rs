@@ -0,0 +1,228 @@
+//! A WebSocket connection to the relay that never blocks the game.
+//!
+//! Natively the socket lives on its own thread, in the browser it is driven by the browser's
+//! event loop. Either way, messages are queued up until the game asks for them.
+
+/// Something received from the relay.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Received {
+    Text(String),
+
+    /// The connection has closed, for the given reason.
+    Closed(String),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::Connection;
+#[cfg(target_arch = "wasm32")]
+pub use web::Connection;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use super::Received;
+    use anyhow::{anyhow, Result};
+    use std::collections::VecDeque;
+    use std::io::ErrorKind;
+    use std::net::TcpStream;
+    use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tungstenite::stream::MaybeTlsStream;
+    use tungstenite::{Message, WebSocket};
+
+    /// How long the socket thread waits for a message before checking for ones to send.
+    const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+    enum Outgoing {
+        Text(String),
+        Close,
+    }
+
+    #[derive(Clone)]
+    pub struct Connection {
+        outgoing: Sender<Outgoing>,
+        incoming: Arc<Mutex<VecDeque<Received>>>,
+    }
+
+    impl Connection {
+        /// Starts connecting to `url` in the background.
+        pub fn connect(url: &str) -> Self {
+            let (outgoing, to_send) = mpsc::channel();
+            let incoming = Arc::new(Mutex::new(VecDeque::new()));
+            let received = Arc::clone(&incoming);
+            let url = url.to_owned();
+            std::thread::spawn(move || {
+                let reason = match run(&url, &to_send, &received) {
+                    Ok(()) => "connection closed".to_owned(),
+                    Err(err) => err.to_string(),
+                };
+                push(&received, Received::Closed(reason));
+            });
+            Self { outgoing, incoming }
+        }
+
+        pub fn send(&self, text: String) {
+            // if the thread has stopped, the closed message is already waiting
+            let _ = self.outgoing.send(Outgoing::Text(text));
+        }
+
+        /// Takes everything received so far.
+        pub fn receive(&self) -> Vec<Received> {
+            match self.incoming.lock() {
+                Ok(mut incoming) => incoming.drain(..).collect(),
+                Err(_) => Vec::new(),
+            }
+        }
+
+        pub fn close(&self) {
+            let _ = self.outgoing.send(Outgoing::Close);
+        }
+    }
+
+    fn push(incoming: &Mutex<VecDeque<Received>>, received: Received) {
+        if let Ok(mut incoming) = incoming.lock() {
+            incoming.push_back(received);
+        }
+    }
+
+    /// Passes messages between the socket and the game until either side closes.
+    fn run(
+        url: &str,
+        to_send: &Receiver<Outgoing>,
+        incoming: &Mutex<VecDeque<Received>>,
+    ) -> Result<()> {
+        let (mut socket, _) = tungstenite::connect(url)?;
+        if let MaybeTlsStream::Plain(stream) = socket.get_mut() {
+            stream.set_read_timeout(Some(POLL_INTERVAL))?;
+            stream.set_nodelay(true)?;
+        }
+
+        loop {
+            loop {
+                match to_send.try_recv() {
+                    Ok(Outgoing::Text(text)) => socket.send(Message::text(text))?,
+                    Ok(Outgoing::Close) | Err(TryRecvError::Disconnected) => {
+                        return close(socket);
+                    }
+                    Err(TryRecvError::Empty) => break,
+                }
+            }
+
+            match socket.read() {
+                Ok(Message::Text(text)) => push(incoming, Received::Text(text.to_string())),
+                Ok(Message::Close(_)) => return Err(anyhow!("the relay closed the connection")),
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(err))
+                    if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    fn close(mut socket: WebSocket<MaybeTlsStream<TcpStream>>) -> Result<()> {
+        socket.close(None)?;
+        // give the relay a moment to acknowledge, but don't wait on it
+        for _ in 0..10 {
+            if socket.read().is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use super::Received;
+    use eframe::wasm_bindgen::closure::Closure;
+    use eframe::wasm_bindgen::JsCast as _;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+    use web_sys::{CloseEvent, MessageEvent, WebSocket};
+
+    #[derive(Clone)]
+    pub struct Connection {
+        socket: Option<WebSocket>,
+
+        /// Messages sent before the socket opened.
+        pending: Rc<RefCell<Vec<String>>>,
+        incoming: Rc<RefCell<VecDeque<Received>>>,
+    }
+
+    impl Connection {
+        /// Starts connecting to `url` in the background.
+        pub fn connect(url: &str) -> Self {
+            let incoming = Rc::new(RefCell::new(VecDeque::new()));
+            let socket = match WebSocket::new(url) {
+                Ok(socket) => socket,
+                Err(err) => {
+                    incoming
+                        .borrow_mut()
+                        .push_back(Received::Closed(format!("{err:?}")));
+                    return Self {
+                        socket: None,
+                        pending: Rc::default(),
+                        incoming,
+                    };
+                }
+            };
+
+            let received = Rc::clone(&incoming);
+            let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                if let Some(text) = event.data().as_string() {
+                    received.borrow_mut().push_back(Received::Text(text));
+                }
+            });
+            socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+            on_message.forget();
+
+            let received = Rc::clone(&incoming);
+            let on_close = Closure::<dyn FnMut(CloseEvent)>::new(move |event: CloseEvent| {
+                let reason = match event.reason() {
+                    reason if reason.is_empty() => "connection closed".to_owned(),
+                    reason => reason,
+                };
+                received.borrow_mut().push_back(Received::Closed(reason));
+            });
+            socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+            on_close.forget();
+
+            Self {
+                socket: Some(socket),
+                pending: Rc::default(),
+                incoming,
+            }
+        }
+
+        pub fn send(&self, text: String) {
+            self.pending.borrow_mut().push(text);
+            self.flush();
+        }
+
+        /// Takes everything received so far.
+        pub fn receive(&self) -> Vec<Received> {
+            self.flush();
+            self.incoming.borrow_mut().drain(..).collect()
+        }
+
+        pub fn close(&self) {
+            if let Some(socket) = &self.socket {
+                let _ = socket.close();
+            }
+        }
+
+        /// Sends the pending messages once the socket has opened.
+        fn flush(&self) {
+            let Some(socket) = &self.socket else {
+                return;
+            };
+            if socket.ready_state() != WebSocket::OPEN {
+                return;
+            }
+            for text in self.pending.borrow_mut().drain(..) {
+                let _ = socket.send_with_str(&text);
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! The relay server that online races are played through.
+//!
+//! The relay knows nothing about the game. It groups players into rooms, picks the seed once a
+//! room is full and forwards each player's buttons to everyone else in the room.
+
+use super::{ClientMessage, RelayMessage, PLAYERS};
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tungstenite::{Message, WebSocket};
+
+/// How long a connection waits for a message before checking for ones to forward.
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+#[derive(Default)]
+struct Room {
+    /// Where to send messages for each player, or `None` once they have left.
+    players: Vec<Option<Sender<String>>>,
+}
+
+impl Room {
+    /// Sends a message to everyone in the room except `from`.
+    fn broadcast(&self, from: usize, message: &RelayMessage) {
+        let text = message.to_text();
+        for (player, sender) in self.players.iter().enumerate() {
+            if let (true, Some(sender)) = (player != from, sender) {
+                let _ = sender.send(text.clone());
+            }
+        }
+    }
+}
+
+/// Rooms still waiting for players, by name.
+type Lobby = Arc<Mutex<HashMap<String, Arc<Mutex<Room>>>>>;
+
+/// Listens on `address` and relays races until the process is stopped.
+pub fn run(address: &str) -> Result<()> {
+    let listener = TcpListener::bind(address)?;
+    info!("relay listening on {}", listener.local_addr()?);
+    serve(listener)
+}
+
+/// Relays races for every connection made to `listener`.
+pub fn serve(listener: TcpListener) -> Result<()> {
+    let lobby = Lobby::default();
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let lobby = Arc::clone(&lobby);
+        std::thread::spawn(move || {
+            if let Err(err) = handle(stream, &lobby) {
+                warn!("connection ended: {err}");
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Serves one player for as long as they stay connected.
+fn handle(stream: TcpStream, lobby: &Lobby) -> Result<()> {
+    stream.set_nodelay(true)?;
+    let mut socket = tungstenite::accept(stream)?;
+    socket.get_mut().set_read_timeout(Some(POLL_INTERVAL))?;
+
+    let (sender, to_send) = mpsc::channel();
+    let mut seat = None;
+    let result = relay(&mut socket, &sender, &to_send, lobby, &mut seat);
+
+    if let Some((room, player)) = seat {
+        leave(lobby, &room, player);
+    }
+    result
+}
+
+/// Passes messages between one player and their room.
+fn relay(
+    socket: &mut WebSocket<TcpStream>,
+    sender: &Sender<String>,
+    to_send: &Receiver<String>,
+    lobby: &Lobby,
+    seat: &mut Option<(Arc<Mutex<Room>>, usize)>,
+) -> Result<()> {
+    loop {
+        loop {
+            match to_send.try_recv() {
+                Ok(text) => socket.send(Message::text(text))?,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Ok(()),
+            }
+        }
+
+        let text = match socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => continue,
+            Err(tungstenite::Error::Io(err))
+                if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+            {
+                continue
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        match (ClientMessage::parse(&text)?, &seat) {
+            (ClientMessage::Join { room }, None) => *seat = Some(join(lobby, &room, sender)),
+            (ClientMessage::Input { tick, input }, Some((room, player))) => {
+                let room = room.lock().map_err(|_| anyhow!("room lock poisoned"))?;
+                room.broadcast(
+                    *player,
+                    &RelayMessage::Input {
+                        player: *player,
+                        tick,
+                        input,
+                    },
+                );
+            }
+            (message, _) => return Err(anyhow!("unexpected message {message:?}")),
+        }
+    }
+}
+
+/// Seats a player in the room called `name`, starting the race if it is now full.
+fn join(lobby: &Lobby, name: &str, sender: &Sender<String>) -> (Arc<Mutex<Room>>, usize) {
+    let mut lobby = lobby.lock().expect("lobby lock poisoned");
+    let room = Arc::clone(lobby.entry(name.to_owned()).or_default());
+    let mut seated = room.lock().expect("room lock poisoned");
+    seated.players.push(Some(sender.clone()));
+    let player = seated.players.len() - 1;
+    info!("player {player} joined room {name}");
+
+    if seated.players.len() == PLAYERS {
+        lobby.remove(name);
+        let seed = rand::random();
+        for (player, sender) in seated.players.iter().enumerate() {
+            if let Some(sender) = sender {
+                let start = RelayMessage::Start {
+                    seed,
+                    player,
+                    players: PLAYERS,
+                };
+                let _ = sender.send(start.to_text());
+            }
+        }
+    }
+    drop(seated);
+    (room, player)
+}
+
+/// Removes a player from their room, telling everyone else they have gone.
+fn leave(lobby: &Lobby, room: &Arc<Mutex<Room>>, player: usize) {
+    let mut lobby = lobby.lock().expect("lobby lock poisoned");
+    let mut seated = room.lock().expect("room lock poisoned");
+    seated.players[player] = None;
+    seated.broadcast(player, &RelayMessage::Left { player });
+
+    // a room nobody is waiting in any more is closed
+    if seated.players.iter().all(Option::is_none) {
+        lobby.retain(|_, waiting| !Arc::ptr_eq(waiting, room));
+    }
+}
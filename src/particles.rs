@@ -0,0 +1,179 @@
+//! # particles
+//! Dust, sand and debris thrown up by the dino.
+//!
+//! Particles only change what is drawn, never how a run plays, and they have their own random
+//! numbers so turning them down doesn't change the obstacles. They live in a fixed size pool:
+//! once it is full, new particles are dropped rather than allocated.
+
+use crate::sprite::{self, Sprite};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+/// The most particles alive at once in a run.
+pub const MAX_PARTICLES: usize = 128;
+
+/// How fast particles fall, in game units per tick per tick.
+const GRAVITY: f64 = 0.4;
+
+/// How strong the effects are. This also scales the screen shake, so `Off` suits players who
+/// prefer reduced motion.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum EffectsIntensity {
+    Off,
+    Low,
+    #[default]
+    Full,
+}
+
+impl EffectsIntensity {
+    pub const ALL: [EffectsIntensity; 3] = [
+        EffectsIntensity::Off,
+        EffectsIntensity::Low,
+        EffectsIntensity::Full,
+    ];
+
+    /// The id of the intensity's name in the [`Locale`](crate::locale::Locale).
+    pub fn message_id(self) -> &'static str {
+        match self {
+            EffectsIntensity::Off => "effects-off",
+            EffectsIntensity::Low => "effects-low",
+            EffectsIntensity::Full => "effects-full",
+        }
+    }
+
+    /// How much of each effect to show, from 0 to 1.
+    pub fn scale(self) -> f32 {
+        match self {
+            EffectsIntensity::Off => 0.0,
+            EffectsIntensity::Low => 0.4,
+            EffectsIntensity::Full => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParticleKind {
+    /// Puffed up when the dino lands.
+    Dust,
+
+    /// Kicked up behind the dino as it runs.
+    Sand,
+
+    /// Thrown out of an obstacle the dino hits.
+    Debris,
+}
+
+impl ParticleKind {
+    pub fn sprite(self) -> Sprite {
+        match self {
+            ParticleKind::Dust | ParticleKind::Sand => sprite::SPECK,
+            ParticleKind::Debris => sprite::CHUNK,
+        }
+    }
+
+    /// How many ticks a particle of this kind lasts.
+    fn life(self) -> u32 {
+        match self {
+            ParticleKind::Dust => 20,
+            ParticleKind::Sand => 14,
+            ParticleKind::Debris => 45,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    pub kind: ParticleKind,
+
+    /// Where the particle is, in game units from the top left of the lane.
+    pub x: f64,
+    pub y: f64,
+    vx: f64,
+    vy: f64,
+
+    /// Ticks since the particle was made.
+    age: u32,
+}
+
+impl Particle {
+    fn is_alive(&self) -> bool {
+        self.age < self.kind.life()
+    }
+
+    /// How much of its life the particle has left, from 1 when made to 0.
+    pub fn remaining(&self) -> f32 {
+        1.0 - self.age as f32 / self.kind.life() as f32
+    }
+}
+
+/// The particles of one run.
+#[derive(Debug, Clone)]
+pub struct Particles {
+    pool: Vec<Particle>,
+    rng: StdRng,
+}
+
+impl Default for Particles {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Particles {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            pool: Vec::with_capacity(MAX_PARTICLES),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// The particles still alive.
+    pub fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.pool.iter().filter(|particle| particle.is_alive())
+    }
+
+    /// Throws out `count` particles at `intensity` from `x`, `y`, in the direction of `vx`,
+    /// `vy` give or take `spread`.
+    pub fn emit(
+        &mut self,
+        kind: ParticleKind,
+        count: u32,
+        intensity: EffectsIntensity,
+        (x, y): (f64, f64),
+        (vx, vy): (f64, f64),
+        spread: f64,
+    ) {
+        let count = (count as f32 * intensity.scale()).round() as u32;
+        for _ in 0..count {
+            let particle = Particle {
+                kind,
+                x,
+                y,
+                vx: vx + self.rng.random_range(-spread..=spread),
+                vy: vy + self.rng.random_range(-spread..=spread),
+                age: 0,
+            };
+            match self.pool.iter().position(|slot| !slot.is_alive()) {
+                Some(slot) => self.pool[slot] = particle,
+                None if self.pool.len() < MAX_PARTICLES => self.pool.push(particle),
+                None => return,
+            }
+        }
+    }
+
+    /// Whether to kick up sand this tick, which happens on some ticks at random.
+    pub(crate) fn roll(&mut self, chance: f32) -> bool {
+        chance > 0.0 && self.rng.random::<f32>() < chance
+    }
+
+    /// Moves every particle on by a tick, with the world scrolling `scroll` game units towards
+    /// the dino.
+    pub fn tick(&mut self, scroll: f64) {
+        for particle in self.pool.iter_mut().filter(|particle| particle.is_alive()) {
+            particle.x += particle.vx - scroll;
+            particle.y += particle.vy;
+            particle.vy += GRAVITY;
+            particle.age += 1;
+        }
+    }
+}
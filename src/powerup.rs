@@ -0,0 +1,97 @@
+//! # powerup
+//! Collectible power-ups, spawned between obstacles when they are turned on in the settings.
+
+use crate::spawner::SPAWN_X;
+use rand::prelude::*;
+
+/// How far down the top of a power-up's badge is, in the same units as the dino's height.
+pub const BADGE_TOP: f64 = 277.0;
+
+/// How long slow motion lasts for, in ticks.
+pub const SLOW_MOTION_TICKS: u32 = 300;
+
+/// How much slow motion scales `dino_speed` by.
+pub const SLOW_MOTION_FACTOR: f64 = 0.6;
+
+/// How long the score multiplier lasts for, in ticks.
+pub const MULTIPLIER_TICKS: u32 = 600;
+
+/// How much the score multiplier scales the score by.
+pub const SCORE_MULTIPLIER: f64 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PowerUpKind {
+    /// Absorbs the next hit.
+    Shield,
+
+    /// Slows the game down for a while.
+    SlowMotion,
+
+    /// Increases the score for a while.
+    ScoreMultiplier,
+}
+
+/// A power-up waiting to be collected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerUp {
+    pub kind: PowerUpKind,
+    pub x: f64,
+}
+
+impl PowerUp {
+    /// Creates a power-up of a random kind at the right edge of the play area.
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let kind = match rng.random_range(0..3) {
+            0 => PowerUpKind::Shield,
+            1 => PowerUpKind::SlowMotion,
+            _ => PowerUpKind::ScoreMultiplier,
+        };
+        Self { kind, x: SPAWN_X }
+    }
+}
+
+/// The power-ups currently affecting the dino.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ActivePowerUps {
+    pub shield: bool,
+
+    /// Ticks of slow motion left.
+    pub slow_motion: u32,
+
+    /// Ticks of score multiplier left.
+    pub multiplier: u32,
+}
+
+impl ActivePowerUps {
+    pub fn collect(&mut self, kind: PowerUpKind) {
+        match kind {
+            PowerUpKind::Shield => self.shield = true,
+            PowerUpKind::SlowMotion => self.slow_motion = SLOW_MOTION_TICKS,
+            PowerUpKind::ScoreMultiplier => self.multiplier = MULTIPLIER_TICKS,
+        }
+    }
+
+    /// Counts down the timed power-ups.
+    pub fn tick(&mut self) {
+        self.slow_motion = self.slow_motion.saturating_sub(1);
+        self.multiplier = self.multiplier.saturating_sub(1);
+    }
+
+    /// What `dino_speed` is scaled by.
+    pub fn speed_factor(&self) -> f64 {
+        if self.slow_motion > 0 {
+            SLOW_MOTION_FACTOR
+        } else {
+            1.0
+        }
+    }
+
+    /// What the score is scaled by.
+    pub fn score_factor(&self) -> f64 {
+        if self.multiplier > 0 {
+            SCORE_MULTIPLIER
+        } else {
+            1.0
+        }
+    }
+}
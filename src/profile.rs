@@ -0,0 +1,303 @@
+//! # profile
+//! Named players sharing one copy of the game, each with their own [`SaveData`].
+//!
+//! Each profile's save is kept under its own key in eframe's storage, which is a file natively
+//! and local storage on the web, so it migrates like any other save. The first profile uses
+//! [`SAVE_KEY`], so progress from before there were profiles becomes the first profile. The
+//! list of profiles is kept under [`PROFILES_KEY`].
+//!
+//! Anything that can't be loaded is left in storage as it is, so a damaged save never takes the
+//! other profiles with it or gets written over by a new game. It stays that way until the player
+//! starts it over or deletes it.
+
+use crate::save::{self, SaveData, SAVE_KEY};
+use anyhow::{bail, Result};
+
+/// Where the list of profiles is kept in eframe's storage.
+pub const PROFILES_KEY: &str = "profiles";
+
+/// The longest name a profile can have, in characters.
+pub const MAX_NAME_LENGTH: usize = 24;
+
+/// One player's name and progress.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    /// Picks the storage key, so renaming a profile doesn't move its save.
+    id: u32,
+    pub name: String,
+    pub save: SaveData,
+
+    /// Why the profile's save couldn't be loaded. A damaged profile can't be played, and its
+    /// save is left alone.
+    damaged: Option<String>,
+}
+
+impl Profile {
+    fn new(id: u32, name: String, save: SaveData) -> Self {
+        Self {
+            id,
+            name,
+            save,
+            damaged: None,
+        }
+    }
+
+    /// Why the profile's save couldn't be loaded, if it couldn't.
+    pub fn damaged(&self) -> Option<&str> {
+        self.damaged.as_deref()
+    }
+}
+
+/// The list of profiles, as it is stored. The saves are stored separately.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct Index {
+    /// The id of the selected profile.
+    current: u32,
+    profiles: Vec<(u32, String)>,
+}
+
+/// Every profile on this machine, with one of them selected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profiles {
+    profiles: Vec<Profile>,
+    current: usize,
+
+    /// Profiles deleted since the last store, whose saves still have to be wiped.
+    deleted: Vec<u32>,
+
+    /// Why the list of profiles couldn't be loaded. Nothing is stored while it is damaged, as
+    /// there is no telling which saves a new list would write over.
+    damaged: Option<String>,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self::new(String::new(), SaveData::default())
+    }
+}
+
+impl Profiles {
+    /// A single profile called `name` with the progress in `save`.
+    pub fn new(name: String, save: SaveData) -> Self {
+        Self {
+            profiles: vec![Profile::new(0, name, save)],
+            current: 0,
+            deleted: Vec::new(),
+            damaged: None,
+        }
+    }
+
+    /// The profiles in `storage`. Without a list of profiles, any progress saved before there
+    /// were profiles becomes a single profile called `name`. If no profile can be played, a new
+    /// one called `name` is added.
+    pub fn load(storage: &dyn eframe::Storage, name: String) -> Self {
+        let index = match storage.get_string(PROFILES_KEY) {
+            Some(text) => match ron::from_str::<Index>(&text) {
+                Ok(index) => index,
+                Err(err) => {
+                    let mut profiles = Self::new(name, SaveData::default());
+                    profiles.damaged = Some(format!("the list of profiles is damaged: {err}"));
+                    return profiles;
+                }
+            },
+            None => Index {
+                current: 0,
+                profiles: vec![(0, name.clone())],
+            },
+        };
+        let mut profiles = Self::new(name.clone(), SaveData::default());
+        profiles.profiles = (index.profiles.into_iter())
+            .map(|(id, name)| {
+                let mut profile = Profile::new(id, name, SaveData::default());
+                let loaded = match id {
+                    // the first profile might still be in the app state from before saves
+                    0 => save::load(storage),
+                    id => storage
+                        .get_string(&key(id))
+                        .filter(|text| !text.is_empty())
+                        .map(|text| SaveData::from_text(&text))
+                        .transpose(),
+                };
+                match loaded {
+                    Ok(save) => profile.save = save.unwrap_or_default(),
+                    Err(err) => {
+                        profile.damaged =
+                            Some(format!("{}'s progress is damaged: {err}", profile.name))
+                    }
+                }
+                profile
+            })
+            .collect();
+        let playable = |profile: &Profile| profile.damaged.is_none();
+        match (profiles.profiles.iter())
+            .position(|profile| profile.id == index.current && playable(profile))
+            .or_else(|| profiles.profiles.iter().position(playable))
+        {
+            Some(current) => profiles.current = current,
+            None => {
+                let mut fresh = name.clone();
+                for n in 2.. {
+                    if profiles.name_problem(&fresh, None).is_none() {
+                        break;
+                    }
+                    fresh = format!("{name} ({n})");
+                }
+                profiles
+                    .create(&fresh)
+                    .expect("a name no profile has is free");
+            }
+        }
+        profiles
+    }
+
+    /// Writes every profile to `storage`, and wipes the saves of deleted ones. Damaged saves are
+    /// left as they are, and nothing is written while the list itself is damaged.
+    pub fn store(&mut self, storage: &mut dyn eframe::Storage) -> Result<()> {
+        if self.damaged.is_some() {
+            return Ok(());
+        }
+        for profile in self
+            .profiles
+            .iter()
+            .filter(|profile| profile.damaged.is_none())
+        {
+            storage.set_string(&key(profile.id), profile.save.to_text()?);
+        }
+        for id in self.deleted.drain(..) {
+            // eframe's storage can't remove keys, so an empty save stands in for none
+            storage.set_string(&key(id), String::new());
+        }
+        let index = Index {
+            current: self.current().id,
+            profiles: (self.profiles.iter())
+                .map(|profile| (profile.id, profile.name.clone()))
+                .collect(),
+        };
+        storage.set_string(PROFILES_KEY, ron::to_string(&index)?);
+        Ok(())
+    }
+
+    /// Why the list of profiles couldn't be loaded, if it couldn't.
+    pub fn damaged(&self) -> Option<&str> {
+        self.damaged.as_deref()
+    }
+
+    /// Gives up on a damaged list of profiles, so the profiles here get stored over it.
+    pub fn start_list_over(&mut self) {
+        self.damaged = None;
+    }
+
+    /// Gives up on the damaged save of the profile at `index`, giving it a new game.
+    pub fn start_over(&mut self, index: usize) {
+        if let Some(profile) = self.profiles.get_mut(index) {
+            profile.damaged = None;
+            profile.save = SaveData::default();
+        }
+    }
+
+    /// The profiles, in the order they were created.
+    pub fn all(&self) -> &[Profile] {
+        &self.profiles
+    }
+
+    /// Where the selected profile is in [`Self::all`].
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn current(&self) -> &Profile {
+        &self.profiles[self.current]
+    }
+
+    pub fn current_mut(&mut self) -> &mut Profile {
+        &mut self.profiles[self.current]
+    }
+
+    /// Selects the profile at `index`, if there is one and it isn't damaged.
+    pub fn select(&mut self, index: usize) {
+        if self
+            .profiles
+            .get(index)
+            .is_some_and(|profile| profile.damaged.is_none())
+        {
+            self.current = index;
+        }
+    }
+
+    /// Why `name` can't be given to the profile at `index`, or to a new one if `index` is
+    /// `None`, as a message id for the [`Locale`](crate::locale::Locale).
+    pub fn name_problem(&self, name: &str, index: Option<usize>) -> Option<&'static str> {
+        let name = name.trim();
+        if name.is_empty() {
+            Some("profile-name-empty")
+        } else if name.chars().count() > MAX_NAME_LENGTH {
+            Some("profile-name-too-long")
+        } else if (self.profiles.iter().enumerate()).any(|(other, profile)| {
+            Some(other) != index && profile.name.to_lowercase() == name.to_lowercase()
+        }) {
+            Some("profile-name-taken")
+        } else {
+            None
+        }
+    }
+
+    /// Adds a profile with a new game and selects it.
+    pub fn create(&mut self, name: &str) -> Result<()> {
+        if let Some(problem) = self.name_problem(name, None) {
+            bail!("can't name a profile {name:?}: {problem}");
+        }
+        // a deleted profile's id waits until its save is wiped, so the wipe can't hit this one
+        let id = (self.profiles.iter().map(|profile| profile.id))
+            .chain(self.deleted.iter().copied())
+            .max()
+            .map_or(0, |id| id + 1);
+        self.profiles.push(Profile::new(
+            id,
+            name.trim().to_owned(),
+            SaveData::default(),
+        ));
+        self.current = self.profiles.len() - 1;
+        Ok(())
+    }
+
+    pub fn rename(&mut self, index: usize, name: &str) -> Result<()> {
+        if let Some(problem) = self.name_problem(name, Some(index)) {
+            bail!("can't name a profile {name:?}: {problem}");
+        }
+        let Some(profile) = self.profiles.get_mut(index) else {
+            bail!("there is no profile {index}");
+        };
+        profile.name = name.trim().to_owned();
+        Ok(())
+    }
+
+    /// Deletes the profile at `index` and its progress. The last profile that can be played
+    /// can't be deleted.
+    pub fn delete(&mut self, index: usize) -> Result<()> {
+        if index >= self.profiles.len() {
+            bail!("there is no profile {index}");
+        }
+        if index == self.current {
+            let Some(next) = (0..self.profiles.len())
+                .find(|&other| other != index && self.profiles[other].damaged.is_none())
+            else {
+                bail!("the last profile can't be deleted");
+            };
+            self.current = next;
+        }
+        let profile = self.profiles.remove(index);
+        self.deleted.push(profile.id);
+        if self.current > index {
+            self.current -= 1;
+        }
+        Ok(())
+    }
+}
+
+/// Where the save of the profile with `id` is kept.
+fn key(id: u32) -> String {
+    match id {
+        0 => SAVE_KEY.to_owned(),
+        id => format!("{SAVE_KEY}-{id}"),
+    }
+}
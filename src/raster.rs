@@ -0,0 +1,181 @@
+//! # raster
+//! Draws runs on the CPU, without a window or a GPU.
+//!
+//! Sprites are copied out of the asset map with nearest-neighbour sampling, using the same
+//! positions the window draws them at, so bots can learn from pixels and runs can be turned
+//! into images.
+
+use crate::app::LANE_HEIGHT;
+use crate::assets::Atlas;
+use crate::batch::Transform;
+use crate::render::{self, RenderTarget, SIZE};
+use crate::run::Run;
+use crate::sprite::{self, Sprite, ATLAS_HEIGHT, ATLAS_WIDTH};
+use crate::theme::Theme;
+use egui::{Color32, Vec2};
+use image::{Rgba, RgbaImage};
+
+/// The part of a lane that gets drawn, in game units.
+pub const FIELD_LEFT: f64 = 0.0;
+pub const FIELD_TOP: f64 = 140.0;
+pub const FIELD_WIDTH: f64 = 1625.0;
+pub const FIELD_HEIGHT: f64 = 210.0;
+
+/// The colour behind everything.
+pub const BACKGROUND: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+/// An image that sprites are drawn into, covering the field from the first lane down.
+#[derive(Debug, Clone)]
+pub struct Raster {
+    image: RgbaImage,
+
+    /// Pixels per game unit.
+    scale: f32,
+
+    background: Rgba<u8>,
+    theme: Option<Theme>,
+
+    /// The asset map to draw from, the built in one unless it is replaced.
+    atlas: Atlas,
+
+    /// The asset map recoloured for the theme, or `None` to draw the asset map as it is.
+    recoloured: Option<RgbaImage>,
+}
+
+impl Raster {
+    /// An empty field, with `scale` pixels for each game unit.
+    pub fn new(scale: f32) -> Self {
+        Self::with_lanes(scale, 1)
+    }
+
+    /// An empty field tall enough for `lanes` players racing, each a lane below the last.
+    pub fn with_lanes(scale: f32, lanes: usize) -> Self {
+        let height = FIELD_HEIGHT + lanes.saturating_sub(1) as f64 * LANE_HEIGHT;
+        let width = (FIELD_WIDTH * scale as f64).round().max(1.0) as u32;
+        let height = (height * scale as f64).round().max(1.0) as u32;
+        Self {
+            image: RgbaImage::from_pixel(width, height, BACKGROUND),
+            scale,
+            background: BACKGROUND,
+            theme: None,
+            atlas: Atlas::built_in(),
+            recoloured: None,
+        }
+    }
+
+    /// Draws from `atlas` instead of the asset map built into the game, such as the one the
+    /// window is drawing from.
+    pub fn set_atlas(&mut self, atlas: Atlas) {
+        self.atlas = atlas;
+        self.recoloured = self
+            .theme
+            .and_then(|theme| theme.recolour(self.atlas.image()));
+    }
+
+    /// Draws in `theme`'s colours from now on, and clears the image to its background.
+    pub fn set_theme(&mut self, theme: Theme) {
+        let [r, g, b, a] = theme.background.to_array();
+        self.background = Rgba([r, g, b, a]);
+        self.theme = Some(theme);
+        self.recoloured = theme.recolour(self.atlas.image());
+        self.clear();
+    }
+
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+
+    pub fn into_image(self) -> RgbaImage {
+        self.image
+    }
+
+    /// Fills the whole image with the background.
+    pub fn clear(&mut self) {
+        for pixel in self.image.pixels_mut() {
+            *pixel = self.background;
+        }
+    }
+
+    /// Clears the image and draws a run where the window draws the first lane.
+    pub fn draw_run(&mut self, run: &Run) {
+        self.clear();
+        render::draw_run(self, run, 0.0, &sprite::dino(run), Color32::WHITE);
+    }
+}
+
+impl RenderTarget for Raster {
+    fn draw_transformed(
+        &mut self,
+        sprite: &Sprite,
+        x: f64,
+        y: f64,
+        scale: f32,
+        tint: Color32,
+        transform: Transform,
+    ) {
+        let atlas = self.recoloured.as_ref().unwrap_or(self.atlas.image());
+        // the target is in screen points, the field in game units
+        let pixels = self.scale as f64 / SIZE as f64;
+        let left = ((x - FIELD_LEFT * SIZE as f64) * pixels).round() as i64;
+        let top = ((y - FIELD_TOP * SIZE as f64) * pixels).round() as i64;
+        let width = (sprite.size.x as f64 * scale as f64 * pixels).round() as i64;
+        let height = (sprite.size.y as f64 * scale as f64 * pixels).round() as i64;
+        if width <= 0 || height <= 0 {
+            return;
+        }
+
+        // every pixel the turned sprite could cover
+        let size = Vec2::new(width as f32, height as f32);
+        let centre = Vec2::new(left as f32, top as f32) + size / 2.0;
+        let reach = transform
+            .rotate(size / 2.0)
+            .abs()
+            .max(transform.rotate(Vec2::new(size.x, -size.y) / 2.0).abs());
+        let bounds = |from: f32, to: f32, limit: u32| {
+            (from.floor() as i64).max(0)..(to.ceil() as i64).min(limit as i64)
+        };
+        let columns = bounds(centre.x - reach.x, centre.x + reach.x, self.image.width());
+        let rows = bounds(centre.y - reach.y, centre.y + reach.y, self.image.height());
+
+        let unturn = Transform {
+            rotation: -transform.rotation,
+            ..Transform::default()
+        };
+        for row in rows {
+            for column in columns.clone() {
+                let along = if transform.rotation == 0.0 {
+                    Vec2::new((column - left) as f32 + 0.5, (row - top) as f32 + 0.5) / size
+                } else {
+                    let from_centre = Vec2::new(column as f32 + 0.5, row as f32 + 0.5) - centre;
+                    unturn.rotate(from_centre) / size + Vec2::splat(0.5)
+                };
+                if !(0.0..=1.0).contains(&along.x) || !(0.0..=1.0).contains(&along.y) {
+                    continue;
+                }
+                let uv = transform.uv(sprite.uv, along);
+                let source_x = ((uv.x * ATLAS_WIDTH) as u32).min(atlas.width() - 1);
+                let source_y = ((uv.y * ATLAS_HEIGHT) as u32).min(atlas.height() - 1);
+                blend(
+                    self.image.get_pixel_mut(column as u32, row as u32),
+                    *atlas.get_pixel(source_x, source_y),
+                    tint,
+                );
+            }
+        }
+    }
+}
+
+/// Draws `source`, multiplied by `tint`, over `target`.
+fn blend(target: &mut Rgba<u8>, source: Rgba<u8>, tint: Color32) {
+    // work premultiplied, like egui does
+    let alpha = source[3] as u32 * tint.a() as u32 / 255;
+    if alpha == 0 {
+        return;
+    }
+    let tint = [tint.r(), tint.g(), tint.b()];
+    for channel in 0..3 {
+        let colour = source[channel] as u32 * source[3] as u32 / 255 * tint[channel] as u32 / 255;
+        target[channel] = (colour + target[channel] as u32 * (255 - alpha) / 255) as u8;
+    }
+    target[3] = (alpha + target[3] as u32 * (255 - alpha) / 255) as u8;
+}
@@ -1,273 +1,338 @@
 //! # render
 //! this
-use crate::app::DinoGame;
-use anyhow::{anyhow, Result};
-use egui::{Color32, Painter, Pos2, Ui};
-use epaint::pos2;
-use epaint::{Mesh, Rect, Vertex};
-use image::ImageReader;
+use crate::assets::Atlas;
+use crate::batch::{SpriteBatch, Transform};
+use crate::particles::Particles;
+use crate::powerup::{
+    ActivePowerUps, PowerUp, PowerUpKind, BADGE_TOP, MULTIPLIER_TICKS, SLOW_MOTION_TICKS,
+};
+use crate::raster::{FIELD_LEFT, FIELD_WIDTH};
+use crate::run::Run;
+use crate::sprite::{self, Sprite};
+use crate::theme::Theme;
+use anyhow::Result;
+use egui::{Color32, Painter, TextureHandle, TextureId};
+use epaint::Rect;
+use epaint::{pos2, Vec2};
 use log::debug;
-use std::io::Cursor;
 
 pub const SIZE: f32 = 0.8;
 
-/// Renders part of the asset map to the painter
-pub fn render(
-    game: &mut DinoGame,
-    x: f64,
-    y: f64,
-    painter: Painter,
-    ctx: &eframe::egui::Context,
-    mut rx: f32,
-    mut ry: f32,
-    loc_size: f32,
-    uv1: Pos2,
-    uv2: Pos2,
-) -> Result<()> {
-    if game.asset_map.is_none() {
-        game.asset_map = Some(ctx.load_texture(
-            "asset_map",
-            {
-                let raw_data = include_bytes!("asset-map.png");
-                let image = (ImageReader::new(Cursor::new(raw_data))
-                    .with_guessed_format()
-                    .expect("Cursor io never fails"))
-                .decode()?;
-
-                let img_size = [image.width() as _, image.height() as _];
-                let image_buffer = image.to_rgba8();
-                let pixels = image_buffer.as_flat_samples();
-                egui::ColorImage::from_rgba_unmultiplied(img_size, pixels.as_slice())
-            },
-            egui::TextureOptions::default(),
-        ))
-    }
-    let texture_id = egui::TextureId::from(&(game.asset_map.clone().expect("no texture id")));
-
-    let x: f32 = x as f32;
-    let y: f32 = y as f32;
-    rx *= loc_size;
-    ry *= loc_size;
-    // 1517/120
-
-    painter.image(
-        //12.6666667
-        texture_id,
-        Rect::from_min_max(pos2(x, y), pos2(x + rx, y + ry)),
-        egui::Rect::from_min_max(uv1, uv2),
-        Color32::WHITE,
+/// How much bigger the score digits are drawn than the other sprites.
+const DIGIT_SCALE: f32 = 5.0 / 4.0 * 0.7;
+
+/// Something sprites from the asset map can be drawn onto: the window, or an image on the CPU.
+///
+/// Positions and sizes are in screen points, the same as the painter's.
+pub trait RenderTarget {
+    /// Draws a sprite with its top left corner at `x`, `y`, `scale` times its size and
+    /// multiplied by `tint`, then turned and mirrored by `transform`.
+    fn draw_transformed(
+        &mut self,
+        sprite: &Sprite,
+        x: f64,
+        y: f64,
+        scale: f32,
+        tint: Color32,
+        transform: Transform,
     );
-    Ok(())
+
+    /// Draws a sprite the right way up.
+    fn draw_sprite(&mut self, sprite: &Sprite, x: f64, y: f64, scale: f32, tint: Color32) {
+        self.draw_transformed(sprite, x, y, scale, tint, Transform::default());
+    }
 }
 
-/// draws the dino at a given x and y
-pub fn draw_dino_rest_state(
-    game: &mut DinoGame,
-    x: f64,
-    y: f64,
-    painter: Painter,
-    _ui: &mut Ui,
-    ctx: &eframe::egui::Context,
-) -> Result<()> {
-    let rx: f32 = 80.0;
-    let ry = 90.0;
-    let scale = SIZE * 1.0;
-    let uv1 = pos2(0.03, 0.0);
-    let uv2 = pos2(0.068, 0.5);
-    render(game, x, y, painter.clone(), ctx, rx, ry, scale, uv1, uv2)?;
-    Ok(())
+/// A sprite waiting in a [`Renderer`]'s display list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteDraw {
+    pub sprite: Sprite,
+
+    /// Where on the screen the sprite goes.
+    pub rect: Rect,
+    pub tint: Color32,
+    pub transform: Transform,
 }
 
-pub fn draw_dino_left(
-    game: &mut DinoGame,
-    x: f64,
-    y: f64,
-    painter: Painter,
-    _ui: &mut Ui,
-    ctx: &eframe::egui::Context,
-) -> Result<()> {
-    let rx: f32 = 88.0;
-    let ry = 90.0;
-    let scale = SIZE * 1.0;
-    let uv1 = pos2(1854.0 / 2446.0, 0.0);
-    let uv2 = pos2(1942.0 / 2446.0, 0.5);
-    render(game, x, y, painter.clone(), ctx, rx, ry, scale, uv1, uv2)?;
-    Ok(())
+/// Draws sprites from the asset map onto the window.
+///
+/// Sprites are collected into a display list while the game is drawn, then handed to egui
+/// together by [`Renderer::finish`]. The renderer owns the asset map's texture, so the game
+/// doesn't have to be borrowed mutably to draw it.
+#[derive(Clone, Default)]
+pub struct Renderer {
+    /// The asset map in the theme's colours, uploaded the first time it is needed.
+    texture: Option<TextureHandle>,
+
+    /// The asset map to draw from, the built in one unless it is replaced.
+    atlas: Atlas,
+    theme: Option<Theme>,
+    list: Vec<SpriteDraw>,
+
+    /// How far every sprite is moved from where it is drawn, to shake the screen.
+    offset: Vec2,
 }
 
-pub fn draw_dino_right(
-    game: &mut DinoGame,
-    x: f64,
-    y: f64,
-    painter: Painter,
-    _ui: &mut Ui,
-    ctx: &eframe::egui::Context,
-) -> Result<()> {
-    let rx: f32 = 88.0;
-    let ry = 90.0;
-    let scale = SIZE * 1.0;
-    let uv1 = pos2(1942.0 / 2446.0, 0.0);
-    let uv2 = pos2(2030.0 / 2446.0, 0.5);
-    render(game, x, y, painter.clone(), ctx, rx, ry, scale, uv1, uv2)?;
-    Ok(())
+impl Renderer {
+    /// Uses `atlas` as the asset map instead of the one built into the game.
+    pub fn set_atlas(&mut self, atlas: Atlas) {
+        self.atlas = atlas;
+        self.texture = None;
+    }
+
+    /// The asset map being drawn from, for drawing the same sprites on the CPU.
+    pub fn atlas(&self) -> &Atlas {
+        &self.atlas
+    }
+
+    /// Draws in `theme`'s colours, recolouring the asset map if the theme has changed.
+    pub fn set_theme(&mut self, theme: Theme) {
+        if self.theme != Some(theme) {
+            self.theme = Some(theme);
+            self.texture = None;
+        }
+    }
+
+    /// Moves every sprite drawn from now on by `offset`.
+    pub fn set_offset(&mut self, offset: Vec2) {
+        self.offset = offset;
+    }
+
+    /// The sprites drawn since the last [`Renderer::finish`], in the order they were drawn.
+    pub fn display_list(&self) -> &[SpriteDraw] {
+        &self.list
+    }
+
+    /// The asset map's texture, uploading it if this is the first time it is needed.
+    fn texture(&mut self, ctx: &egui::Context) -> Result<TextureId> {
+        if self.texture.is_none() {
+            let atlas = self.atlas.image();
+            let recoloured = self.theme.and_then(|theme| theme.recolour(atlas));
+            let image = recoloured.as_ref().unwrap_or(atlas);
+            let size = [image.width() as _, image.height() as _];
+            let pixels = image.as_flat_samples();
+            self.texture = Some(ctx.load_texture(
+                "asset_map",
+                egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice()),
+                egui::TextureOptions::default(),
+            ));
+        }
+        Ok(self.texture.as_ref().expect("just loaded").id())
+    }
+
+    /// Draws the display list onto `painter` as one batch and empties it.
+    pub fn finish(&mut self, painter: &Painter) -> Result<()> {
+        if self.list.is_empty() {
+            return Ok(());
+        }
+        let mut batch = SpriteBatch::new(self.texture(painter.ctx())?);
+        for draw in self.list.drain(..) {
+            batch.push(draw.rect, draw.sprite.uv, draw.tint, draw.transform);
+        }
+        batch.submit(painter);
+        Ok(())
+    }
 }
 
-pub fn draw_dino_still(
-    game: &mut DinoGame,
-    x: f64,
-    y: f64,
-    painter: Painter,
-    _ui: &mut Ui,
-    ctx: &eframe::egui::Context,
-) -> Result<()> {
-    let rx: f32 = 88.0;
-    let ry = 90.0;
-    let scale = SIZE * 1.0;
-    let uv1 = pos2(0.686_274_5, 0.0);
-    let uv2 = pos2(0.720_996_74, 0.5);
-    render(game, x, y, painter.clone(), ctx, rx, ry, scale, uv1, uv2)?;
-    Ok(())
+impl RenderTarget for Renderer {
+    fn draw_transformed(
+        &mut self,
+        sprite: &Sprite,
+        x: f64,
+        y: f64,
+        scale: f32,
+        tint: Color32,
+        transform: Transform,
+    ) {
+        self.list.push(SpriteDraw {
+            sprite: *sprite,
+            rect: Rect::from_min_size(pos2(x as f32, y as f32) + self.offset, sprite.size * scale),
+            tint,
+            transform,
+        });
+    }
 }
 
-pub fn draw_cacti_small(
-    game: &mut DinoGame,
-    x: f64,
-    y: f64,
-    painter: &Painter,
-    _ui: &mut Ui,
-    ctx: &eframe::egui::Context,
-) -> Result<()> {
-    let rx: f32 = 40.0;
-    let ry = 90.0;
-    let scale = SIZE * 1.0;
-    let uv1 = pos2(446.0 / 2446.0, 0.0);
-    let uv2 = pos2(480.0 / 2446.0, 0.5);
-    render(game, x, y, painter.clone(), ctx, rx, ry, scale, uv1, uv2)?;
-    Ok(())
+/// Draws a run `y` game units below where a single player's run goes: its score and power-up
+/// timers, the obstacles and power-ups, the dino as `dino` and the floor. The score, timers and
+/// floor fade in with the intro.
+pub fn draw_run(target: &mut impl RenderTarget, run: &Run, y: f64, dino: &Sprite, tint: Color32) {
+    let fade = Color32::WHITE.gamma_multiply(run.intro().fade());
+    if fade.a() > 0 {
+        draw_numbers(
+            target,
+            &((run.score() / 85.0) as i32).to_string(),
+            1500.0 * (SIZE as f64),
+            (210.0 + y) * SIZE as f64,
+            false,
+            fade,
+        );
+        draw_power_up_timers(
+            target,
+            &run.active,
+            882.0 * (SIZE as f64),
+            (202.0 + y) * SIZE as f64,
+            fade,
+        );
+    }
+
+    for enemy in run.enemies() {
+        target.draw_sprite(
+            &sprite::CACTUS_SMALL,
+            enemy.start_x * (SIZE as f64),
+            (271.0 + y) * (SIZE as f64),
+            SIZE,
+            Color32::WHITE,
+        );
+    }
+
+    for power_up in &run.power_ups {
+        draw_power_up(target, power_up, y);
+    }
+
+    draw_dino(target, run, dino, y, tint);
+
+    if fade.a() > 0 {
+        draw_floor(target, run.dino_distance, y, fade);
+    }
+
+    draw_particles(target, run.particles(), y);
 }
 
-pub fn draw_floor(
-    game: &mut DinoGame,
-    x: f64,
-    y: f64,
-    painter: &Painter,
-    _ui: &mut Ui,
-    ctx: &eframe::egui::Context,
-) -> Result<()> {
-    let rx: f32 = 2400.0;
-    let ry = 25.0;
-    let scale = SIZE * 1.0;
-    let uv1 = pos2(2.0 / 2446.0, 102.0 / 194.0);
-    let uv2 = pos2(2402.0 / 2446.0, 128.0 / 194.0);
-    render(game, x, y, painter.clone(), ctx, rx, ry, scale, uv1, uv2)?;
-    Ok(())
+/// Draws the particles of a run `y` game units below the first lane, fading as they age.
+pub fn draw_particles(target: &mut impl RenderTarget, particles: &Particles, y: f64) {
+    for particle in particles.iter() {
+        target.draw_sprite(
+            &particle.kind.sprite(),
+            particle.x * (SIZE as f64),
+            (particle.y + y) * (SIZE as f64),
+            SIZE,
+            Color32::WHITE.gamma_multiply(particle.remaining()),
+        );
+    }
 }
 
-fn get_number_cords(number: f32) -> Result<[f32; 2]> {
-    match number {
-        0.0 => Ok([1292.0, 1314.0]),
-        1.0 => Ok([1314.0, 1334.0]),
-        2.0 => Ok([1332.0, 1354.0]),
-        3.0 => Ok([1352.0, 1374.0]),
-        4.0 => Ok([1372.0, 1394.0]),
-        5.0 => Ok([1392.0, 1414.0]),
-        6.0 => Ok([1412.0, 1434.0]),
-        7.0 => Ok([1432.0, 1454.0]),
-        8.0 => Ok([1452.0, 1474.0]),
-        9.0 => Ok([1472.0, 1494.0]),
-        10.0 => Ok([1492.0, 1534.0]),
-        _ => Err(anyhow!("Invalid number")),
+/// Draws "GAME OVER" and the restart icon in the middle of the play field, `y` game units
+/// below the first lane, faded by `tint`.
+pub fn draw_game_over(target: &mut impl RenderTarget, y: f64, tint: Color32) {
+    let centre = FIELD_LEFT + FIELD_WIDTH / 2.0;
+    for (sprite, top) in [(sprite::GAME_OVER, 190.0), (sprite::RESTART, 230.0)] {
+        target.draw_sprite(
+            &sprite,
+            (centre - sprite.size.x as f64 / 2.0) * (SIZE as f64),
+            (top + y) * (SIZE as f64),
+            SIZE,
+            tint,
+        );
     }
 }
 
+/// Draws the dino of a run `y` game units below the first lane.
+pub fn draw_dino(target: &mut impl RenderTarget, run: &Run, dino: &Sprite, y: f64, tint: Color32) {
+    target.draw_sprite(
+        dino,
+        30.0 * (SIZE as f64),
+        (run.dino_y() + 150.0 + y) * (SIZE as f64),
+        SIZE,
+        tint,
+    );
+}
+
+/// Draws the floor scrolled along by `distance`, `y` game units below the first lane. Two
+/// copies of the floor are drawn so it wraps around.
+pub fn draw_floor(target: &mut impl RenderTarget, distance: f64, y: f64, tint: Color32) {
+    let scrolled = distance % 2400.0;
+    let y = (320.0 + y) * SIZE as f64;
+    target.draw_sprite(
+        &sprite::FLOOR,
+        (30.0 + 2400.0 - scrolled - 20.0) * (SIZE as f64),
+        y,
+        SIZE,
+        tint,
+    );
+    target.draw_sprite(
+        &sprite::FLOOR,
+        (30.0 - scrolled) * (SIZE as f64),
+        y,
+        SIZE,
+        tint,
+    );
+}
+
+/// Draws a number padded to four digits, after "HI" if `high_score` is set. Characters that
+/// aren't digits are drawn as "HI".
 pub fn draw_numbers(
-    mut numbers: String,
-    game: &mut DinoGame,
+    target: &mut impl RenderTarget,
+    numbers: &str,
     x: f64,
     y: f64,
-    painter: &Painter,
-    ui: &mut Ui,
-    ctx: &mut eframe::egui::Context,
-    score: bool,
-) -> Result<()> {
-    let mut space: f32 = 0.0;
-    for _ in 0..(4 - numbers.chars().count()).max(0) {
-        numbers = "0".to_owned() + &numbers;
-    }
+    high_score: bool,
+    tint: Color32,
+) {
+    let padding = 4_usize.saturating_sub(numbers.chars().count());
+    let digits = high_score
+        .then_some(sprite::HI)
+        .into_iter()
+        .chain(std::iter::repeat(0).take(padding))
+        .chain(
+            numbers
+                .chars()
+                .map(|c| c.to_digit(10).map_or(sprite::HI, |d| d as usize)),
+        );
+    debug!("{}", numbers);
 
-    if score {
-        let c_float = 10 as f32;
-        debug!("number as float: {}", c_float);
-        let gap = get_number_cords(c_float)?;
-        draw_number(
-            c_float,
-            game,
-            x + space as f64,
-            y,
-            painter,
-            ui,
-            &mut ctx.clone(),
-        )
-        .unwrap();
-        space += (gap[1] - gap[0] + 20.0) * 0.7;
+    let mut space: f32 = 0.0;
+    for digit in digits {
+        let (Some(sprite), Some(advance)) = (sprite::digit(digit), sprite::digit_advance(digit))
+        else {
+            continue;
+        };
+        target.draw_sprite(&sprite, x + space as f64, y, SIZE * DIGIT_SCALE, tint);
+        space += advance * 0.7;
     }
+}
 
-    debug!("{}", numbers);
-    for c in numbers.chars() {
-        let c_float = (c.to_digit(10).or(Some(10))).unwrap() as f32;
-        debug!("number as float: {}", c_float);
-        let gap = get_number_cords(c_float)?;
-        draw_number(
-            c_float,
-            game,
-            x + space as f64,
-            y,
-            painter,
-            ui,
-            &mut ctx.clone(),
-        )
-        .unwrap();
-        space += (gap[1] - gap[0] + 3.0) * 0.7;
-    }
-    Ok(())
+/// Draws a power-up waiting to be collected, `y` game units below the first lane.
+pub fn draw_power_up(target: &mut impl RenderTarget, power_up: &PowerUp, y: f64) {
+    target.draw_sprite(
+        &sprite::power_up(power_up.kind),
+        (power_up.x + 2.0) * (SIZE as f64),
+        (BADGE_TOP + y) * (SIZE as f64),
+        SIZE,
+        Color32::WHITE,
+    );
 }
 
-pub fn draw_number(
-    number: f32,
-    game: &mut DinoGame,
+/// Draws a badge for each active power-up from `x` along, with a bar under it showing how
+/// long it has left.
+pub fn draw_power_up_timers(
+    target: &mut impl RenderTarget,
+    active: &ActivePowerUps,
     x: f64,
     y: f64,
-    painter: &Painter,
-    _ui: &mut Ui,
-    ctx: &mut eframe::egui::Context,
-) -> Result<()> {
-    let cords = get_number_cords(number)?;
-    let rx: f32 = cords[1] - cords[0] + 2.0;
-    let ry = 25.0;
-    let scale = SIZE * 5.0 / 4.0 * 0.7;
-    let uv1 = pos2((cords[0] + 1.0) / 2446.0, 0.0 / 194.0);
-    let uv2 = pos2((cords[1] - 1.0) / 2446.0, 25.0 / 194.0);
-    render(game, x, y, painter.clone(), ctx, rx, ry, scale, uv1, uv2)?;
-    Ok(())
-}
+    tint: Color32,
+) {
+    let timers = [
+        (PowerUpKind::Shield, if active.shield { 1.0 } else { 0.0 }),
+        (
+            PowerUpKind::SlowMotion,
+            active.slow_motion as f32 / SLOW_MOTION_TICKS as f32,
+        ),
+        (
+            PowerUpKind::ScoreMultiplier,
+            active.multiplier as f32 / MULTIPLIER_TICKS as f32,
+        ),
+    ];
 
-pub fn draw_white(x: f64, y: f64, _dx: f64, _dy: f64, _painter: &Painter) -> Result<()> {
-    let mut mesh = Mesh::default();
-    let scale = SIZE * 1.0;
-    let colour = Color32::from_rgb(255, 255, 255);
-    let mut points = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
-
-    for point in points.iter_mut() {
-        let vn = Vertex {
-            pos: Pos2::new(
-                ((point[0] * scale) as f64 + x) as f32,
-                ((point[1] * scale) as f64 + y) as f32,
-            ),
-            color: colour,
-            uv: Pos2::new(0.0, 0.0),
-        };
-        mesh.vertices.push(vn);
+    let mut x = x;
+    for (kind, left) in timers {
+        if left <= 0.0 {
+            continue;
+        }
+        let badge = sprite::power_up(kind);
+        target.draw_sprite(&badge, x, y, SIZE, tint);
+        let below = y + (badge.size.y + 4.0) as f64 * SIZE as f64;
+        target.draw_sprite(&sprite::timer_bar(left), x, below, SIZE, tint);
+        x += (badge.size.x * 1.5) as f64 * SIZE as f64;
     }
-    mesh.indices.extend_from_slice(&[0, 1, 2, 1, 2, 3]);
-    Ok(())
 }
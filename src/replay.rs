@@ -0,0 +1,203 @@
+//! # replay
+//! Every button a player held during a run, so the run can be played back and checked.
+//!
+//! Runs are deterministic: the same seed, mode, settings and buttons always give the same run.
+//! A replay stores those along with the score it claims, and [`Replay::verify`] plays it back
+//! to see whether the claim holds.
+
+use crate::assist::Assists;
+use crate::controller::{Controller, View};
+use crate::input::Input;
+use crate::mode::GameMode;
+use crate::run::Run;
+use crate::settings::Settings;
+use anyhow::{anyhow, Context, Result};
+
+/// The first line of every replay file.
+const HEADER: &str = "dino-replay 1";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    pub mode: GameMode,
+
+    /// The settings the run was played with. Only the ones that change how the game plays matter.
+    pub settings: Settings,
+
+    /// The buttons held when the run was started.
+    pub held: Input,
+
+    /// The buttons held on each tick.
+    pub inputs: Vec<Input>,
+
+    /// The score the run ended with.
+    pub score: f64,
+}
+
+impl Replay {
+    /// An empty replay of a run that is about to start.
+    pub fn new(seed: u64, mode: GameMode, settings: Settings, held: Input) -> Self {
+        Self {
+            seed,
+            mode,
+            settings,
+            held,
+            inputs: Vec::new(),
+            score: 0.0,
+        }
+    }
+
+    /// Plays a whole run with `controller`, stopping after `max_ticks` if it is still going.
+    pub fn record(
+        seed: u64,
+        mode: GameMode,
+        settings: Settings,
+        controller: &mut dyn Controller,
+        max_ticks: u32,
+    ) -> Result<Self> {
+        let mut replay = Self::new(seed, mode, settings, Input::default());
+        let mut run = replay.start();
+        while !run.is_finished() && replay.inputs.len() < max_ticks as usize {
+            let input = controller.act(&View {
+                run: &run,
+                ui: None,
+            });
+            run.step(input, &replay.settings, mode)?;
+            replay.inputs.push(input);
+        }
+        replay.score = run.score();
+        Ok(replay)
+    }
+
+    /// The run as it was when it started.
+    pub(crate) fn start(&self) -> Run {
+        let mut run = Run::new(self.seed);
+        run.held = self.held;
+        let _ = run.jump();
+        run
+    }
+
+    /// Plays the replay back, stopping early if the run finishes before the buttons run out.
+    pub fn play(&self) -> Result<Run> {
+        let mut run = self.start();
+        for input in &self.inputs {
+            if run.is_finished() {
+                break;
+            }
+            run.step(*input, &self.settings, self.mode)?;
+        }
+        Ok(run)
+    }
+
+    /// Plays the replay back and checks that it ends with the score it claims, using every
+    /// button it holds.
+    pub fn verify(&self) -> Result<Run> {
+        let mut run = self.start();
+        for (tick, input) in self.inputs.iter().enumerate() {
+            if run.is_finished() {
+                return Err(anyhow!(
+                    "the run finished on tick {tick} of {}",
+                    self.inputs.len()
+                ));
+            }
+            run.step(*input, &self.settings, self.mode)?;
+        }
+        if run.score() != self.score {
+            return Err(anyhow!(
+                "the replay claims a score of {} but played back to {}",
+                self.score,
+                run.score()
+            ));
+        }
+        Ok(run)
+    }
+
+    pub fn to_text(&self) -> String {
+        let settings = &self.settings;
+        let inputs: String = self
+            .inputs
+            .iter()
+            .map(|input| char::from(b'0' + input.to_bits()))
+            .collect();
+        format!(
+            "{HEADER}\nseed {}\nmode {:?}\nsettings {} {} {} {} {}\nheld {}\nscore {}\ninputs {inputs}\n",
+            self.seed,
+            self.mode,
+            settings.jump_buffer,
+            settings.coyote_time,
+            settings.power_ups,
+            settings.assists.game_speed,
+            settings.assists.jump_buffer,
+            self.held.to_bits(),
+            self.score,
+        )
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut lines = text.lines();
+        if lines.next().map(str::trim) != Some(HEADER) {
+            return Err(anyhow!("not a replay"));
+        }
+        let mut field = |name: &str| -> Result<Vec<&str>> {
+            let line = lines
+                .next()
+                .with_context(|| format!("replay is missing {name}"))?;
+            let mut words = line.split_whitespace();
+            if words.next() != Some(name) {
+                return Err(anyhow!("expected {name} but found {line}"));
+            }
+            Ok(words.collect())
+        };
+
+        let seed = single(field("seed")?)?.parse()?;
+        let mode = single(field("mode")?)?;
+        let mode = GameMode::ALL
+            .into_iter()
+            .find(|known| format!("{known:?}") == mode)
+            .with_context(|| format!("unknown mode {mode}"))?;
+        let [jump_buffer, coyote_time, power_ups, game_speed, extra_buffer] =
+            field("settings")?[..]
+        else {
+            return Err(anyhow!("settings need five values"));
+        };
+        let settings = Settings {
+            jump_buffer: jump_buffer.parse()?,
+            coyote_time: coyote_time.parse()?,
+            power_ups: power_ups.parse()?,
+            assists: Assists {
+                game_speed: game_speed.parse()?,
+                jump_buffer: extra_buffer.parse()?,
+                ..Assists::default()
+            },
+            ..Settings::default()
+        };
+        let held = Input::from_bits(single(field("held")?)?.parse()?);
+        let score = single(field("score")?)?.parse()?;
+        let inputs = field("inputs")?
+            .concat()
+            .chars()
+            .map(|c| {
+                c.to_digit(4)
+                    .map(|bits| Input::from_bits(bits as u8))
+                    .with_context(|| format!("{c} is not a button"))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            seed,
+            mode,
+            settings,
+            held,
+            inputs,
+            score,
+        })
+    }
+}
+
+/// The only value on a line.
+fn single(values: Vec<&str>) -> Result<&str> {
+    match values[..] {
+        [value] => Ok(value),
+        _ => Err(anyhow!("expected one value, found {}", values.len())),
+    }
+}
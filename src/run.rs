@@ -0,0 +1,408 @@
+//! # run
+//! One dino's run: its physics, the obstacles coming at it and its score.
+//!
+//! Every player gets their own run, so two dinos given the same seed race over the same course.
+
+use crate::animation::{self, Animator};
+use crate::app::{
+    Enemy, CLEAR_Y, DROP_SPEED, FAST_FALL_GRAVITY, GRAVITY, GROUND_Y, HITBOX_BACK, HITBOX_FRONT,
+    JUMP_SPEED, MAX_JUMP_Y, MIN_JUMP_Y, REACH_Y, SPEED_GAIN, SPEED_SCALE,
+};
+use crate::input::Input;
+use crate::intro::Intro;
+use crate::mode::{GameMode, TIME_ATTACK_TICKS};
+use crate::particles::{EffectsIntensity, ParticleKind, Particles};
+use crate::powerup::{ActivePowerUps, PowerUp};
+use crate::settings::Settings;
+use crate::spawner::{Spawn, Spawner};
+use anyhow::Result;
+
+/// How fast the dino runs when a run starts.
+pub(crate) const START_SPEED: f64 = 25.0;
+
+/// Where the dino's feet touch the floor, in game units from the top left of the lane.
+const FEET: (f64, f64) = (60.0, 336.0);
+
+/// Where the dino's nose is when it runs into something.
+const NOSE: (f64, f64) = (110.0, 290.0);
+
+#[derive(Clone)]
+pub struct Run {
+    pub(crate) dino_speed_y: f64,
+    pub(crate) dino_y: f64,
+
+    /// Set while holding the jump button can still make the dino rise higher.
+    jumping: bool,
+    reached_min_height: bool,
+    fast_falling: bool,
+
+    /// The buttons held during the previous tick, so presses and releases can be told apart.
+    pub(crate) held: Input,
+
+    /// Ticks left in which a jump pressed in the air will still happen on landing.
+    buffered_jump: u32,
+
+    /// Ticks in a row the dino has been touching an obstacle.
+    touching: u32,
+
+    pub(crate) dino_distance: f64,
+
+    /// The distance travelled, scaled by any score multiplier.
+    pub(crate) score: f64,
+
+    /// Ticks since the run started.
+    pub(crate) run_ticks: u32,
+
+    pub(crate) dino_speed: f64,
+
+    /// How fast the game scrolls compared to normal, lowered by the game speed assist.
+    game_speed: f64,
+    pub(crate) enemys: Vec<Enemy>,
+    pub(crate) power_ups: Vec<PowerUp>,
+    pub(crate) active: ActivePowerUps,
+    pub(crate) tick: i32,
+    spawner: Spawner,
+    intro: Intro,
+
+    /// Set once the dino has hit an obstacle or run out of time.
+    pub(crate) finished: bool,
+
+    /// The dino's animation.
+    animator: Animator,
+
+    particles: Particles,
+}
+
+impl Default for Run {
+    fn default() -> Self {
+        Self::with_spawner(Spawner::default())
+    }
+}
+
+impl Run {
+    /// Creates a run whose obstacles are determined by `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            particles: Particles::new(seed),
+            ..Self::with_spawner(Spawner::new(seed))
+        }
+    }
+
+    fn with_spawner(spawner: Spawner) -> Self {
+        Self {
+            dino_speed_y: 0.0,
+            dino_y: GROUND_Y,
+            jumping: false,
+            reached_min_height: false,
+            fast_falling: false,
+            held: Input::default(),
+            buffered_jump: 0,
+            touching: 0,
+            dino_distance: 0.0,
+            score: 0.0,
+            run_ticks: 0,
+            dino_speed: START_SPEED,
+            game_speed: 1.0,
+            enemys: Vec::new(),
+            power_ups: Vec::new(),
+            active: ActivePowerUps::default(),
+            tick: 0,
+            spawner,
+            intro: Intro::default(),
+            finished: false,
+            animator: Animator::new(&animation::BLINK_IDLE),
+            particles: Particles::default(),
+        }
+    }
+
+    /// Whether the dino is standing on the floor, and so able to jump.
+    pub fn on_ground(&self) -> bool {
+        self.dino_y == GROUND_Y
+    }
+
+    /// Whether the dino is moving upwards.
+    pub fn rising(&self) -> bool {
+        self.dino_speed_y < 0.0
+    }
+
+    /// Whether the dino is high enough to pass over obstacles.
+    pub fn clears_obstacles(&self) -> bool {
+        self.dino_y < CLEAR_Y
+    }
+
+    /// Whether the run is over for this dino.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    /// How high the dino is. It stands on the floor at 100 and goes up as this goes down.
+    pub fn dino_y(&self) -> f64 {
+        self.dino_y
+    }
+
+    pub fn animator(&self) -> &Animator {
+        &self.animator
+    }
+
+    pub fn intro(&self) -> &Intro {
+        &self.intro
+    }
+
+    pub fn particles(&self) -> &Particles {
+        &self.particles
+    }
+
+    /// Moves the particles on without the rest of the run, for once the run is over.
+    pub(crate) fn settle(&mut self) {
+        self.particles.tick(0.0);
+    }
+
+    /// Shows everything straight away rather than waiting for the intro to finish.
+    pub fn skip_intro(&mut self) {
+        self.intro.skip();
+    }
+
+    /// Plays the idle animation for a tick while waiting for the run to start.
+    pub(crate) fn idle(&mut self) {
+        self.animator.play(&animation::BLINK_IDLE);
+        self.animator.advance(1.0);
+    }
+
+    /// How far everything scrolls towards the dino each tick.
+    pub fn distance_per_tick(&self) -> f64 {
+        self.dino_speed * self.active.speed_factor() * self.game_speed * SPEED_SCALE
+    }
+
+    /// The obstacles that can still hit the dino, in no particular order.
+    pub fn enemies(&self) -> impl Iterator<Item = &Enemy> {
+        self.enemys.iter().filter(|enemy| !enemy.ignore)
+    }
+
+    pub(crate) fn jump(&mut self) -> Result<()> {
+        if self.dino_y != GROUND_Y {
+            return Ok(());
+        };
+        self.dino_speed_y = JUMP_SPEED;
+        self.jumping = true;
+        self.reached_min_height = false;
+        Ok(())
+    }
+
+    /// Stops the jump button from making the dino rise any higher.
+    fn end_jump(&mut self) {
+        self.dino_speed_y = self.dino_speed_y.max(DROP_SPEED);
+        self.jumping = false;
+    }
+
+    /// Moves the dino for one tick, then reacts to the buttons held during it.
+    pub(crate) fn move_dino(&mut self, input: Input, settings: &Settings) -> Result<()> {
+        // gravity
+        if self.dino_y < GROUND_Y {
+            self.dino_speed_y += if self.fast_falling {
+                FAST_FALL_GRAVITY
+            } else {
+                GRAVITY
+            };
+        } else {
+            self.dino_y = GROUND_Y;
+            self.dino_speed_y = 0.0_f64.min(self.dino_speed_y);
+        };
+        self.dino_y = GROUND_Y.min(self.dino_y + self.dino_speed_y);
+
+        if self.dino_y == GROUND_Y && self.dino_speed_y >= 0.0 {
+            self.jumping = false;
+            self.fast_falling = false;
+        }
+
+        if self.jumping {
+            if self.dino_y < MIN_JUMP_Y {
+                self.reached_min_height = true;
+            }
+            let released = !input.jump && self.reached_min_height;
+            if self.dino_y < MAX_JUMP_Y || released {
+                self.end_jump();
+            }
+        }
+
+        if input.jump && !self.held.jump {
+            self.buffered_jump = settings.jump_buffer + settings.assists.jump_buffer + 1;
+        }
+        if self.buffered_jump > 0 {
+            self.buffered_jump -= 1;
+            if self.dino_y == GROUND_Y {
+                self.buffered_jump = 0;
+                self.jump()?;
+            }
+        }
+
+        if input.duck && self.dino_y < GROUND_Y && !self.fast_falling {
+            self.end_jump();
+            self.fast_falling = true;
+            self.dino_speed_y = self.dino_speed_y.max(GRAVITY);
+        }
+
+        self.held = input;
+        Ok(())
+    }
+
+    /// Advances the run by one tick with the given buttons held.
+    pub fn step(&mut self, input: Input, settings: &Settings, mode: GameMode) -> Result<()> {
+        self.dino_speed += SPEED_GAIN;
+        self.active.tick();
+        self.game_speed = settings.assists.speed_factor();
+        let speed = self.dino_speed * self.active.speed_factor() * self.game_speed;
+
+        //enemy spawning
+        if self.tick > 0 || self.dino_y < GROUND_Y {
+            self.tick += 1;
+            self.run_ticks += 1;
+            self.dino_distance += speed * SPEED_SCALE;
+            self.score += speed * SPEED_SCALE * self.active.score_factor();
+
+            match self.spawner.tick(self.dino_speed, speed * SPEED_SCALE) {
+                Some(Spawn::Enemy(enemy)) => self.enemys.push(enemy),
+                Some(Spawn::PowerUp(power_up)) if settings.power_ups => {
+                    self.power_ups.push(power_up)
+                }
+                _ => {}
+            }
+        }
+
+        let was_on_ground = self.on_ground();
+        self.move_dino(input, settings)?;
+
+        let mut hit = false;
+        let mut broken = Vec::new();
+        let mut crashed = false;
+        let mut kill = Vec::new();
+        for enemy in self.enemys.iter_mut() {
+            enemy.start_x -= speed * SPEED_SCALE;
+            enemy.end_x -= speed * SPEED_SCALE;
+
+            // if the enemy is off screen, remove it to save resources
+            if enemy.end_x < -80.0 {
+                enemy.ignore = true;
+                kill.push(*enemy);
+            }
+
+            if !enemy.ignore
+                & (self.dino_y >= CLEAR_Y)
+                & ((enemy.start_x < HITBOX_FRONT) & (enemy.end_x > HITBOX_BACK))
+            {
+                if self.active.shield {
+                    // the shield breaks the obstacle
+                    self.active.shield = false;
+                    enemy.ignore = true;
+                    broken.push(enemy.start_x);
+                } else {
+                    hit = true;
+                }
+            }
+        }
+
+        let (active, dino_y) = (&mut self.active, self.dino_y);
+        self.power_ups.retain_mut(|power_up| {
+            power_up.x -= speed * SPEED_SCALE;
+            if (HITBOX_BACK..HITBOX_FRONT).contains(&power_up.x) && dino_y > REACH_Y {
+                active.collect(power_up.kind);
+                return false;
+            }
+            power_up.x > -80.0
+        });
+
+        if hit && mode.can_die() {
+            self.touching += 1;
+            if self.touching > settings.coyote_time {
+                self.finished = true;
+                crashed = true;
+            }
+        } else {
+            self.touching = 0;
+        }
+
+        if mode == GameMode::TimeAttack && self.run_ticks >= TIME_ATTACK_TICKS {
+            self.finished = true;
+        }
+
+        for to_rem in kill {
+            if let Some(index) = self.enemys.iter().position(|value| *value == to_rem) {
+                self.enemys.swap_remove(index);
+            }
+        }
+
+        let landed = !was_on_ground && self.on_ground();
+        self.kick_up(landed, crashed, &broken, settings.effects);
+        self.particles.tick(speed * SPEED_SCALE);
+
+        self.intro.tick(self.tick > 0);
+        self.animate(input);
+        Ok(())
+    }
+
+    /// Throws up particles for what happened this tick: dust if the dino `landed`, sand as it
+    /// runs, and debris if it `crashed` or from obstacles `broken` with a shield.
+    fn kick_up(
+        &mut self,
+        landed: bool,
+        crashed: bool,
+        broken: &[f64],
+        intensity: EffectsIntensity,
+    ) {
+        if intensity == EffectsIntensity::Off {
+            return;
+        }
+        if landed {
+            let velocity = (0.0, -1.5);
+            self.particles
+                .emit(ParticleKind::Dust, 8, intensity, FEET, velocity, 1.5);
+        }
+        let running = self.tick > 0 && self.on_ground() && !self.finished;
+        if running && self.particles.roll(0.5 * intensity.scale()) {
+            let velocity = (-2.0, -3.0);
+            self.particles.emit(
+                ParticleKind::Sand,
+                1,
+                EffectsIntensity::Full,
+                FEET,
+                velocity,
+                1.0,
+            );
+        }
+        if crashed {
+            let at = (NOSE.0, self.dino_y + NOSE.1 - GROUND_Y);
+            self.particles
+                .emit(ParticleKind::Debris, 12, intensity, at, (3.0, -6.0), 3.0);
+        }
+        for x in broken {
+            self.particles.emit(
+                ParticleKind::Debris,
+                8,
+                intensity,
+                (*x, NOSE.1),
+                (4.0, -5.0),
+                3.0,
+            );
+        }
+    }
+
+    /// Picks the dino's animation for what it did this tick. The legs move faster as the
+    /// dino speeds up.
+    fn animate(&mut self, input: Input) {
+        let clip = if self.finished {
+            &animation::DEAD
+        } else if !self.on_ground() {
+            &animation::JUMP
+        } else if input.duck {
+            &animation::DUCK_RUN
+        } else {
+            &animation::RUN
+        };
+        self.animator.play(clip);
+        self.animator
+            .advance(self.dino_speed * self.game_speed / START_SPEED);
+    }
+}
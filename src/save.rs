@@ -0,0 +1,190 @@
+//! # save
+//! The player's progress, as it is stored and moved between devices.
+//!
+//! Everything worth keeping goes in a [`SaveData`], which records the version of the format it
+//! was written in. Loading reads that version first and migrates older saves up to the current
+//! one, so a save from any release still loads. Before saves had a version the whole app was
+//! stored as it was; that layout is version 1.
+//!
+//! Saves are RON. To move one by hand it is exported as a `.ron` file, or as a save code: the
+//! same RON in base64 after [`CODE_PREFIX`], which fits on one line and survives being pasted
+//! into a chat.
+
+use crate::mode::{DailyRecord, GameMode, RunHistory};
+use crate::net;
+use crate::settings::Settings;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use std::collections::HashMap;
+
+/// The version of the format saves are written in.
+pub const SAVE_VERSION: u32 = 2;
+
+/// Where the first [profile](crate::profile)'s save is kept in eframe's storage.
+pub const SAVE_KEY: &str = "save";
+
+/// What save codes start with, to tell them from save files.
+pub const CODE_PREFIX: &str = "dino-save:";
+
+/// The name exported saves are given.
+pub const FILE_NAME: &str = "dino-save.ron";
+
+/// Everything about the player that outlives the app being closed.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SaveData {
+    /// The version of the format this was written in.
+    pub version: u32,
+
+    /// The mode picked on the main menu.
+    pub mode: GameMode,
+
+    /// The best score in each mode.
+    pub best_scores: HashMap<GameMode, f64>,
+
+    /// The best score in each mode of runs played with assists.
+    pub assisted_scores: HashMap<GameMode, f64>,
+
+    pub daily: DailyRecord,
+    pub history: RunHistory,
+    pub two_player: bool,
+    pub bot_opponent: bool,
+    pub relay: String,
+    pub room: String,
+    pub settings: Settings,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            version: SAVE_VERSION,
+            mode: GameMode::default(),
+            best_scores: HashMap::new(),
+            assisted_scores: HashMap::new(),
+            daily: DailyRecord::default(),
+            history: RunHistory::default(),
+            two_player: false,
+            bot_opponent: false,
+            relay: net::DEFAULT_RELAY.to_owned(),
+            room: "dino".to_owned(),
+            settings: Settings::default(),
+        }
+    }
+}
+
+impl SaveData {
+    /// The save as RON, for storage and save files.
+    pub fn to_text(&self) -> Result<String> {
+        Ok(ron::ser::to_string_pretty(
+            self,
+            ron::ser::PrettyConfig::default(),
+        )?)
+    }
+
+    /// The save as a one line code to copy and paste.
+    pub fn to_code(&self) -> Result<String> {
+        let text = ron::to_string(self)?;
+        Ok(format!("{CODE_PREFIX}{}", BASE64_STANDARD.encode(text)))
+    }
+
+    /// Reads a save file or save code from any version, migrating it to the current one.
+    pub fn from_text(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let text = match text.strip_prefix(CODE_PREFIX) {
+            Some(code) => {
+                let bytes = BASE64_STANDARD
+                    .decode(code.trim())
+                    .context("the save code is damaged")?;
+                String::from_utf8(bytes).context("the save code is damaged")?
+            }
+            None => text.to_owned(),
+        };
+        let version = ron::from_str::<Version>(&text)
+            .map_err(|err| anyhow!("this isn't a save: {err}"))?
+            .version;
+        match version {
+            1 => Ok(ron::from_str::<SaveV1>(&text)?.migrate()),
+            SAVE_VERSION => Ok(ron::from_str(&text)?),
+            newer => bail!("the save is from a newer version of the game (format {newer})"),
+        }
+    }
+}
+
+/// Just the version of a save, read before the rest to know how to read it.
+#[derive(serde::Deserialize)]
+struct Version {
+    #[serde(default = "first_version")]
+    version: u32,
+}
+
+/// Saves from before there was a version are version 1.
+fn first_version() -> u32 {
+    1
+}
+
+/// Version 1: the whole app as it was, with the current mode's best score kept apart from the
+/// others and a few fields that were never progress.
+#[derive(serde::Deserialize)]
+#[serde(default)]
+struct SaveV1 {
+    high_score: f64,
+    mode: GameMode,
+    best_scores: HashMap<GameMode, f64>,
+    daily: DailyRecord,
+    assisted_scores: HashMap<GameMode, f64>,
+    history: RunHistory,
+    two_player: bool,
+    bot_opponent: bool,
+    relay: String,
+    room: String,
+    settings: Settings,
+}
+
+impl Default for SaveV1 {
+    fn default() -> Self {
+        let save = SaveData::default();
+        Self {
+            high_score: 0.0,
+            mode: save.mode,
+            best_scores: save.best_scores,
+            daily: save.daily,
+            assisted_scores: save.assisted_scores,
+            history: save.history,
+            two_player: save.two_player,
+            bot_opponent: save.bot_opponent,
+            relay: save.relay,
+            room: save.room,
+            settings: save.settings,
+        }
+    }
+}
+
+impl SaveV1 {
+    fn migrate(self) -> SaveData {
+        let mut best_scores = self.best_scores;
+        let best = best_scores.entry(self.mode).or_default();
+        *best = best.max(self.high_score);
+        SaveData {
+            version: 2,
+            mode: self.mode,
+            best_scores,
+            assisted_scores: self.assisted_scores,
+            daily: self.daily,
+            history: self.history,
+            two_player: self.two_player,
+            bot_opponent: self.bot_opponent,
+            relay: self.relay,
+            room: self.room,
+            settings: self.settings,
+        }
+    }
+}
+
+/// Reads the save from eframe's storage, falling back to the app state stored before there
+/// were saves. `None` if nothing has been saved yet.
+pub fn load(storage: &dyn eframe::Storage) -> Result<Option<SaveData>> {
+    let text = storage
+        .get_string(SAVE_KEY)
+        .or_else(|| storage.get_string(eframe::APP_KEY));
+    text.map(|text| SaveData::from_text(&text)).transpose()
+}
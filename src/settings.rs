@@ -0,0 +1,245 @@
+//! # settings
+//! Options the player can change from the settings screen. These are persisted with the rest of
+//! the app state.
+
+use crate::assist::Assists;
+use crate::clip::ClipFormat;
+use crate::input::KeyBindings;
+use crate::locale::{Language, Locale};
+use crate::particles::EffectsIntensity;
+use crate::theme::{CustomColours, Theme, ThemeKind};
+
+/// Options that change how the game plays.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Settings {
+    /// How many ticks a jump pressed in the air is remembered for, so it still happens if the
+    /// dino lands in time.
+    pub jump_buffer: u32,
+
+    /// How many ticks in a row the dino may touch an obstacle before it counts as a hit, so a
+    /// jump pressed a moment too late still gets away. This widens the hitbox rather than
+    /// giving a window to jump in after leaving the ground, so it is off by default and keeps
+    /// the gaps between obstacles as the spawner measured them.
+    pub coyote_time: u32,
+
+    /// Whether power-ups appear. Turning them off gives the classic game.
+    pub power_ups: bool,
+
+    /// Options that make the game easier, which keep a run from setting a best score.
+    pub assists: Assists,
+
+    /// How many ticks ahead buttons are sent in online races. More hides more lag, but makes
+    /// the dino slower to respond.
+    pub input_delay: u32,
+
+    /// The keys for playing alone.
+    pub keys: KeyBindings,
+
+    /// The keys each player uses in a two player race.
+    pub race_keys: [KeyBindings; 2],
+
+    /// Whether the autopilot plays under the main menu.
+    pub attract_mode: bool,
+
+    /// The language of the menus, or `None` to follow the system or browser.
+    pub language: Option<Language>,
+
+    /// The colours of the play field.
+    pub theme: ThemeKind,
+
+    /// The colours of the custom theme.
+    pub custom_colours: CustomColours,
+
+    /// How much dust, debris and screen shake there is.
+    pub effects: EffectsIntensity,
+
+    /// Whether screen readers are told about each obstacle as it comes up.
+    pub narrate_obstacles: bool,
+
+    /// Whether runs start with the intro, or with the whole play field already showing.
+    pub play_intro: bool,
+
+    /// How many seconds from the end of a run go into a saved clip, or 0 for the whole run.
+    pub clip_seconds: u32,
+
+    /// What kind of file clips are saved as.
+    pub clip_format: ClipFormat,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            jump_buffer: 6,
+            coyote_time: 0,
+            power_ups: false,
+            assists: Assists::default(),
+            input_delay: 4,
+            keys: KeyBindings::single(),
+            race_keys: KeyBindings::split(),
+            attract_mode: true,
+            language: None,
+            theme: ThemeKind::default(),
+            custom_colours: CustomColours::default(),
+            effects: EffectsIntensity::default(),
+            play_intro: true,
+            narrate_obstacles: false,
+            clip_seconds: 5,
+            clip_format: ClipFormat::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Draws the controls for changing the settings.
+    pub fn show(&mut self, ui: &mut egui::Ui, locale: &Locale) {
+        let system = locale.tr_with(
+            "language-system",
+            &[("language", Language::detect().name().into())],
+        );
+        egui::ComboBox::from_label(locale.tr("language"))
+            .selected_text(self.language.map_or(system.clone(), Language::label))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.language, None, system);
+                for language in Language::ALL {
+                    ui.selectable_value(&mut self.language, Some(language), language.label());
+                }
+            });
+        ui.add(egui::Slider::new(&mut self.jump_buffer, 0..=20).text(locale.tr("jump-buffer")))
+            .on_hover_text(locale.tr("forgiveness-hint"));
+        ui.add(egui::Slider::new(&mut self.coyote_time, 0..=6).text(locale.tr("coyote-time")))
+            .on_hover_text(locale.tr("forgiveness-hint"));
+        ui.checkbox(&mut self.power_ups, locale.tr("power-ups"))
+            .on_hover_text(locale.tr("power-ups-hint"));
+        ui.add(egui::Slider::new(&mut self.input_delay, 0..=15).text(locale.tr("input-delay")))
+            .on_hover_text(locale.tr("input-delay-hint"));
+        ui.collapsing(locale.tr("keys"), |ui| {
+            show_keys(ui, locale, locale.tr("keys-alone"), &mut self.keys);
+            for (player, keys) in self.race_keys.iter_mut().enumerate() {
+                let label = locale.tr_with("player", &[("player", (player + 1).into())]);
+                show_keys(ui, locale, label, keys);
+            }
+        })
+        .header_response
+        .on_hover_text(locale.tr("keys-hint"));
+        ui.checkbox(&mut self.attract_mode, locale.tr("attract-mode"));
+        egui::ComboBox::from_label(locale.tr("field-theme"))
+            .selected_text(locale.tr(self.theme.message_id()))
+            .show_ui(ui, |ui| {
+                for theme in ThemeKind::ALL {
+                    ui.selectable_value(&mut self.theme, theme, locale.tr(theme.message_id()));
+                }
+            });
+        if self.theme == ThemeKind::Custom {
+            locale.row(ui, |ui| {
+                ui.label(locale.tr("theme-sprites"));
+                ui.color_edit_button_srgb(&mut self.custom_colours.foreground);
+                ui.label(locale.tr("theme-background"));
+                ui.color_edit_button_srgb(&mut self.custom_colours.background);
+            });
+        }
+        egui::ComboBox::from_label(locale.tr("effects"))
+            .selected_text(locale.tr(self.effects.message_id()))
+            .show_ui(ui, |ui| {
+                for effects in EffectsIntensity::ALL {
+                    ui.selectable_value(
+                        &mut self.effects,
+                        effects,
+                        locale.tr(effects.message_id()),
+                    );
+                }
+            })
+            .response
+            .on_hover_text(locale.tr("effects-hint"));
+        ui.checkbox(&mut self.narrate_obstacles, locale.tr("narrate-obstacles"))
+            .on_hover_text(locale.tr("narrate-obstacles-hint"));
+        ui.checkbox(&mut self.play_intro, locale.tr("play-intro"))
+            .on_hover_text(locale.tr("play-intro-hint"));
+        ui.add(egui::Slider::new(&mut self.clip_seconds, 0..=30).text(locale.tr("clip-length")))
+            .on_hover_text(locale.tr("clip-length-hint"));
+        egui::ComboBox::from_label(locale.tr("clip-format"))
+            .selected_text(locale.tr(self.clip_format.message_id()))
+            .show_ui(ui, |ui| {
+                for &format in ClipFormat::AVAILABLE {
+                    ui.selectable_value(
+                        &mut self.clip_format,
+                        format,
+                        locale.tr(format.message_id()),
+                    );
+                }
+            });
+        if ui.button(locale.tr("reset-settings")).clicked() {
+            *self = Self::default();
+        }
+    }
+
+    /// Whether runs are easier or harder than the standard game, so they can't set a best
+    /// score: an assist is on, or the jump buffer or coyote time isn't the default.
+    pub fn assisted(&self) -> bool {
+        let standard = Self::default();
+        self.assists.any()
+            || self.jump_buffer != standard.jump_buffer
+            || self.coyote_time != standard.coyote_time
+    }
+
+    /// Whether runs play the standard game, the one best scores and the daily challenge are
+    /// measured in: nothing is [`assisted`](Self::assisted) and power-ups are off.
+    pub fn standard_rules(&self) -> bool {
+        !self.assisted() && !self.power_ups
+    }
+
+    /// The language to show the menus in.
+    pub fn language(&self) -> Language {
+        self.language.unwrap_or_else(Language::detect)
+    }
+
+    /// The colours to draw the play field in.
+    pub fn theme(&self) -> Theme {
+        Theme::new(self.theme, self.custom_colours)
+    }
+}
+
+/// Draws a button for each of the game's buttons, showing the keys that press it. Clicking one
+/// waits for a key, which replaces them, or for escape to leave them as they were.
+fn show_keys(ui: &mut egui::Ui, locale: &Locale, label: String, keys: &mut KeyBindings) {
+    locale.row(ui, |ui| {
+        ui.label(&label);
+        for (id, bound) in [("key-jump", &mut keys.jump), ("key-duck", &mut keys.duck)] {
+            let waiting_id = ui.make_persistent_id((&label, id));
+            let mut waiting = ui.data(|data| data.get_temp(waiting_id)).unwrap_or(false);
+            let pressed = if waiting { pressed_key(ui) } else { None };
+            if let Some(key) = pressed {
+                if key != egui::Key::Escape {
+                    *bound = vec![key];
+                }
+                waiting = false;
+            }
+            let text = if waiting {
+                locale.tr("keys-waiting")
+            } else {
+                let names: Vec<_> = bound.iter().map(|key| key.name()).collect();
+                format!("{}: {}", locale.tr(id), names.join(" / "))
+            };
+            // a key that was just bound can also click the focused button
+            if ui.button(text).clicked() && pressed.is_none() {
+                waiting = !waiting;
+            }
+            ui.data_mut(|data| data.insert_temp(waiting_id, waiting));
+        }
+    });
+}
+
+/// The key pressed this frame, if any.
+fn pressed_key(ui: &egui::Ui) -> Option<egui::Key> {
+    ui.input(|input| {
+        input.events.iter().find_map(|event| match event {
+            egui::Event::Key {
+                key,
+                pressed: true,
+                repeat: false,
+                ..
+            } => Some(*key),
+            _ => None,
+        })
+    })
+}
@@ -0,0 +1,182 @@
+//! # spawner
+//! Decides when and what obstacles appear.
+//!
+//! Instead of rolling a die every tick, the spawner measures the distance travelled since the
+//! last obstacle and only spawns once a gap picked from a reachable range has been covered. The
+//! range is derived from the jump arc at the current `dino_speed`, so a perfect player can always
+//! land from one jump and take off again before the next obstacle arrives.
+
+use crate::app::{Enemy, HITBOX_FRONT, SPEED_GAIN, SPEED_SCALE};
+use crate::input::Input;
+use crate::powerup::PowerUp;
+use crate::run::{Run, START_SPEED};
+use crate::settings::Settings;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::sync::OnceLock;
+
+/// Where new obstacles appear on the x axis.
+pub const SPAWN_X: f64 = 1800.0;
+
+/// Extra ticks of slack added to the minimum gap to absorb the rounding of a tick based simulation.
+const GAP_MARGIN_TICKS: f64 = 3.0;
+
+/// How much larger than the minimum gap the maximum gap is, as in the original game.
+const MAX_GAP_COEFFICIENT: f64 = 1.5;
+
+/// The chance of a power-up appearing in the gap after an obstacle.
+const POWER_UP_CHANCE: f64 = 0.15;
+
+/// The shape of a full jump, measured by stepping the same physics `tick_game` uses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JumpArc {
+    /// Ticks from the jump input until the dino is back on the ground and can jump again.
+    ///
+    /// Jump has to be let go of before it can be pressed again, so this is only reachable when
+    /// jump is released while the dino is falling.
+    pub total_ticks: u32,
+
+    /// Ticks during which the dino is high enough to pass over an obstacle.
+    pub clear_ticks: u32,
+
+    /// Ticks from the jump input until the dino is first high enough to pass over an obstacle.
+    pub rise_ticks: u32,
+}
+
+impl JumpArc {
+    /// The arc of a full jump, measured the first time it is needed.
+    pub fn full() -> Self {
+        static ARC: OnceLock<JumpArc> = OnceLock::new();
+        *ARC.get_or_init(Self::measure)
+    }
+
+    /// Simulates a full jump from the ground, with jump held until the dino starts falling.
+    pub fn measure() -> Self {
+        let mut game = Run::default();
+        let settings = Settings::default();
+        let held = Input {
+            jump: true,
+            duck: false,
+        };
+        let _ = game.move_dino(held, &settings);
+        let mut total_ticks = 0;
+        let mut clear_ticks = 0;
+        let mut rise_ticks = None;
+        loop {
+            let input = if game.rising() {
+                held
+            } else {
+                Input::default()
+            };
+            let _ = game.move_dino(input, &settings);
+            total_ticks += 1;
+            if game.clears_obstacles() {
+                clear_ticks += 1;
+                rise_ticks.get_or_insert(total_ticks);
+            }
+            if game.on_ground() {
+                break;
+            }
+        }
+        Self {
+            total_ticks,
+            clear_ticks,
+            rise_ticks: rise_ticks.unwrap_or(total_ticks),
+        }
+    }
+}
+
+/// Something the spawner has put at the right edge of the play area.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Spawn {
+    Enemy(Enemy),
+    PowerUp(PowerUp),
+}
+
+/// Spaces obstacles so that every sequence it produces can be cleared.
+#[derive(Clone)]
+pub struct Spawner {
+    rng: StdRng,
+
+    /// Distance travelled since the last obstacle was spawned.
+    travelled: f64,
+
+    /// Distance that has to be travelled before the next obstacle is spawned.
+    next_gap: f64,
+
+    /// A power-up to spawn once `travelled` reaches the distance it is paired with.
+    ///
+    /// Power-ups are put half way through a gap, so collecting one never gets in the way of
+    /// clearing an obstacle. They are rolled for whether or not they are turned on, so the
+    /// obstacles for a seed are the same either way.
+    power_up: Option<(f64, PowerUp)>,
+}
+
+impl Default for Spawner {
+    fn default() -> Self {
+        Self::new(rand::rng().random())
+    }
+}
+
+impl Spawner {
+    /// Creates a spawner whose obstacle sequence is fully determined by `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            travelled: 0.0,
+            next_gap: 0.0,
+            power_up: None,
+        }
+    }
+
+    /// The smallest gap between the fronts of two obstacles that can be cleared with two
+    /// separate jumps at `dino_speed`.
+    ///
+    /// The dino keeps accelerating while an obstacle scrolls towards it, so the gap is sized for
+    /// the fastest the dino can be going by the time the second obstacle arrives. Runs never go
+    /// slower than they start, and that estimate falls apart at a crawl, so slower speeds get
+    /// the gap for the starting speed, which is wider than they need.
+    pub fn min_gap(&self, dino_speed: f64) -> f64 {
+        let dino_speed = dino_speed.max(START_SPEED);
+        let gap_ticks = JumpArc::full().total_ticks as f64 + GAP_MARGIN_TICKS;
+        let travel_ticks = (SPAWN_X - HITBOX_FRONT) / (dino_speed * SPEED_SCALE);
+        // arrival = dino_speed + SPEED_GAIN * (travel_ticks + longest gap in ticks), where the
+        // longest gap is itself measured at the arrival speed.
+        let arrival_speed = (dino_speed + SPEED_GAIN * travel_ticks)
+            / (1.0 - SPEED_GAIN * gap_ticks * MAX_GAP_COEFFICIENT / dino_speed);
+        gap_ticks * arrival_speed * SPEED_SCALE
+    }
+
+    /// The largest gap the spawner will leave, so the game never goes quiet.
+    pub fn max_gap(&self, dino_speed: f64) -> f64 {
+        self.min_gap(dino_speed) * MAX_GAP_COEFFICIENT
+    }
+
+    /// Advances the spawner by `distance`, the distance covered in one tick, returning whatever
+    /// is due to appear.
+    ///
+    /// Gaps are sized for `dino_speed` even when the game is slowed down, as it may speed back up
+    /// before the next obstacle arrives.
+    pub fn tick(&mut self, dino_speed: f64, distance: f64) -> Option<Spawn> {
+        self.travelled += distance;
+        if self.travelled < self.next_gap {
+            return match self.power_up {
+                Some((at, power_up)) if self.travelled >= at => {
+                    self.power_up = None;
+                    Some(Spawn::PowerUp(power_up))
+                }
+                _ => None,
+            };
+        }
+
+        self.travelled = 0.0;
+        let min_gap = self.min_gap(dino_speed);
+        self.next_gap = self.rng.random_range(min_gap..=self.max_gap(dino_speed));
+        let enemy = Enemy::random(&mut self.rng);
+        self.power_up = self
+            .rng
+            .random_bool(POWER_UP_CHANCE)
+            .then(|| (self.next_gap / 2.0, PowerUp::random(&mut self.rng)));
+        Some(Spawn::Enemy(enemy))
+    }
+}
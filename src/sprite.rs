@@ -0,0 +1,155 @@
+//! # sprite
+//! Where each sprite is in the asset map, and how big it is drawn.
+//!
+//! The window and the CPU rasterizer both draw from this table, so a sprite only has to be found
+//! in the asset map once.
+
+use crate::powerup::PowerUpKind;
+use crate::run::Run;
+use epaint::{pos2, vec2, Rect, Vec2};
+
+/// The size of the asset map in pixels.
+pub const ATLAS_WIDTH: f32 = 2446.0;
+pub const ATLAS_HEIGHT: f32 = 194.0;
+
+/// A part of the asset map.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprite {
+    /// Where the sprite is in the asset map, from 0 to 1 on each axis.
+    pub uv: Rect,
+
+    /// The size the sprite is drawn at, before scaling.
+    pub size: Vec2,
+}
+
+/// A sprite from its position in the asset map in pixels, usable in constants.
+macro_rules! atlas {
+    ($min_x:expr, $min_y:expr, $max_x:expr, $max_y:expr, $size:expr) => {
+        Sprite {
+            uv: Rect::from_min_max(
+                pos2($min_x / ATLAS_WIDTH, $min_y / ATLAS_HEIGHT),
+                pos2($max_x / ATLAS_WIDTH, $max_y / ATLAS_HEIGHT),
+            ),
+            size: $size,
+        }
+    };
+}
+
+impl Sprite {
+    /// A sprite from its position in the asset map in pixels.
+    fn from_atlas(min_x: f32, min_y: f32, max_x: f32, max_y: f32, size: Vec2) -> Self {
+        atlas!(min_x, min_y, max_x, max_y, size)
+    }
+}
+
+pub const DINO_REST: Sprite = Sprite {
+    uv: Rect::from_min_max(pos2(0.03, 0.0), pos2(0.068, 0.5)),
+    size: vec2(80.0, 90.0),
+};
+
+pub const DINO_LEFT: Sprite = atlas!(1854.0, 0.0, 1942.0, 97.0, vec2(88.0, 90.0));
+
+pub const DINO_RIGHT: Sprite = atlas!(1942.0, 0.0, 2030.0, 97.0, vec2(88.0, 90.0));
+
+pub const DINO_STILL: Sprite = Sprite {
+    uv: Rect::from_min_max(pos2(0.686_274_5, 0.0), pos2(0.720_996_74, 0.5)),
+    size: vec2(88.0, 90.0),
+};
+
+/// The standing dino with its eyes shut.
+pub const DINO_BLINK: Sprite = atlas!(1766.0, 0.0, 1854.0, 97.0, vec2(88.0, 90.0));
+
+pub const DINO_DEAD: Sprite = atlas!(2118.0, 0.0, 2206.0, 97.0, vec2(88.0, 90.0));
+
+pub const DINO_DUCK_LEFT: Sprite = atlas!(2206.0, 0.0, 2324.0, 97.0, vec2(118.0, 90.0));
+
+pub const DINO_DUCK_RIGHT: Sprite = atlas!(2324.0, 0.0, 2442.0, 97.0, vec2(118.0, 90.0));
+
+pub const BIRD_UP: Sprite = atlas!(260.0, 0.0, 352.0, 84.0, vec2(92.0, 80.0));
+
+pub const BIRD_DOWN: Sprite = atlas!(352.0, 0.0, 444.0, 84.0, vec2(92.0, 80.0));
+
+pub const CACTUS_SMALL: Sprite = atlas!(446.0, 0.0, 480.0, 97.0, vec2(40.0, 90.0));
+
+/// The "GAME OVER" text.
+pub const GAME_OVER: Sprite = atlas!(1294.0, 28.0, 1676.0, 50.0, vec2(382.0, 22.0));
+
+/// The first frame of the restart button's animation.
+pub const RESTART: Sprite = atlas!(218.0, 130.0, 290.0, 194.0, vec2(72.0, 64.0));
+
+/// A few pixels from the middle of a cactus, for dust and sand.
+pub const SPECK: Sprite = atlas!(461.0, 40.0, 465.0, 44.0, vec2(4.0, 4.0));
+
+/// A bigger piece of cactus, for debris.
+pub const CHUNK: Sprite = atlas!(459.0, 30.0, 467.0, 38.0, vec2(8.0, 8.0));
+
+/// The badges of power-ups, which all have the same size.
+pub const SHIELD: Sprite = atlas!(600.0, 140.0, 636.0, 176.0, vec2(36.0, 36.0));
+pub const SLOW_MOTION: Sprite = atlas!(640.0, 140.0, 676.0, 176.0, vec2(36.0, 36.0));
+pub const SCORE_MULTIPLIER: Sprite = atlas!(680.0, 140.0, 716.0, 176.0, vec2(36.0, 36.0));
+
+/// A full timer bar under an active power-up's badge.
+const TIMER_BAR: Sprite = atlas!(720.0, 140.0, 756.0, 144.0, vec2(36.0, 4.0));
+
+pub const FLOOR: Sprite = atlas!(2.0, 102.0, 2402.0, 128.0, vec2(2400.0, 25.0));
+
+/// The "HI" in front of the high score.
+pub const HI: usize = 10;
+
+/// The left and right edge of each digit in the asset map, with [`HI`] after the digits.
+const DIGITS: [[f32; 2]; 11] = [
+    [1292.0, 1314.0],
+    [1314.0, 1334.0],
+    [1332.0, 1354.0],
+    [1352.0, 1374.0],
+    [1372.0, 1394.0],
+    [1392.0, 1414.0],
+    [1412.0, 1434.0],
+    [1432.0, 1454.0],
+    [1452.0, 1474.0],
+    [1472.0, 1494.0],
+    [1492.0, 1534.0],
+];
+
+/// The sprite for a digit of the score, or [`HI`].
+pub fn digit(digit: usize) -> Option<Sprite> {
+    let [left, right] = *DIGITS.get(digit)?;
+    Some(Sprite::from_atlas(
+        left + 1.0,
+        0.0,
+        right - 1.0,
+        25.0,
+        vec2(right - left + 2.0, 25.0),
+    ))
+}
+
+/// How far along the next digit of a number starts after `digit`, before scaling.
+pub fn digit_advance(digit: usize) -> Option<f32> {
+    let [left, right] = *DIGITS.get(digit)?;
+    let gap = if digit == HI { 20.0 } else { 3.0 };
+    Some(right - left + gap)
+}
+
+/// The badge of a kind of power-up.
+pub fn power_up(kind: PowerUpKind) -> Sprite {
+    match kind {
+        PowerUpKind::Shield => SHIELD,
+        PowerUpKind::SlowMotion => SLOW_MOTION,
+        PowerUpKind::ScoreMultiplier => SCORE_MULTIPLIER,
+    }
+}
+
+/// The timer bar cut down to the part of a power-up's time that is `left`, from 0 to 1.
+pub fn timer_bar(left: f32) -> Sprite {
+    let left = left.clamp(0.0, 1.0);
+    let uv = TIMER_BAR.uv;
+    Sprite {
+        uv: Rect::from_min_max(uv.min, pos2(uv.lerp_inside(vec2(left, 1.0)).x, uv.max.y)),
+        size: vec2(TIMER_BAR.size.x * left, TIMER_BAR.size.y),
+    }
+}
+
+/// The dino's current animation frame.
+pub fn dino(run: &Run) -> Sprite {
+    run.animator().sprite()
+}
@@ -0,0 +1,134 @@
+//! # theme
+//! The colours of the play field.
+//!
+//! The asset map is drawn in one grey on white. A theme swaps those for its own colours by
+//! mapping every pixel of the asset map from the grey to the theme's foreground and from white
+//! to its background, so sprites keep their shading. Themes only cover the play field; the
+//! menus follow egui's light or dark mode.
+
+use egui::Color32;
+use image::{Rgba, RgbaImage};
+
+/// The grey the asset map's sprites are drawn in.
+const ATLAS_INK: u8 = 83;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum ThemeKind {
+    /// Grey on white, like the original game.
+    #[default]
+    Classic,
+
+    /// Light grey on nearly black.
+    Dark,
+
+    /// White on black.
+    HighContrast,
+
+    /// The player's own colours.
+    Custom,
+}
+
+impl ThemeKind {
+    pub const ALL: [ThemeKind; 4] = [
+        ThemeKind::Classic,
+        ThemeKind::Dark,
+        ThemeKind::HighContrast,
+        ThemeKind::Custom,
+    ];
+
+    /// The id of the theme's name in the [`Locale`](crate::locale::Locale).
+    pub fn message_id(self) -> &'static str {
+        match self {
+            ThemeKind::Classic => "theme-classic",
+            ThemeKind::Dark => "theme-dark",
+            ThemeKind::HighContrast => "theme-high-contrast",
+            ThemeKind::Custom => "theme-custom",
+        }
+    }
+}
+
+/// The colours a custom theme uses, as sRGB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct CustomColours {
+    pub foreground: [u8; 3],
+    pub background: [u8; 3],
+}
+
+impl Default for CustomColours {
+    fn default() -> Self {
+        Self {
+            foreground: [40, 90, 60],
+            background: [240, 230, 200],
+        }
+    }
+}
+
+/// The colours the play field is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// What the sprites' grey becomes.
+    pub foreground: Color32,
+    pub background: Color32,
+}
+
+impl Theme {
+    pub const CLASSIC: Theme = Theme {
+        foreground: Color32::from_gray(ATLAS_INK),
+        background: Color32::WHITE,
+    };
+
+    pub const DARK: Theme = Theme {
+        foreground: Color32::from_gray(172),
+        background: Color32::from_rgb(32, 33, 36),
+    };
+
+    pub const HIGH_CONTRAST: Theme = Theme {
+        foreground: Color32::WHITE,
+        background: Color32::BLACK,
+    };
+
+    pub fn new(kind: ThemeKind, custom: CustomColours) -> Self {
+        match kind {
+            ThemeKind::Classic => Self::CLASSIC,
+            ThemeKind::Dark => Self::DARK,
+            ThemeKind::HighContrast => Self::HIGH_CONTRAST,
+            ThemeKind::Custom => {
+                let [r, g, b] = custom.foreground;
+                let foreground = Color32::from_rgb(r, g, b);
+                let [r, g, b] = custom.background;
+                Self {
+                    foreground,
+                    background: Color32::from_rgb(r, g, b),
+                }
+            }
+        }
+    }
+
+    /// The asset map in this theme's colours, or `None` if it is already in them.
+    pub fn recolour(&self, atlas: &RgbaImage) -> Option<RgbaImage> {
+        if *self == Self::CLASSIC {
+            return None;
+        }
+        let mut recoloured = atlas.clone();
+        for pixel in recoloured.pixels_mut() {
+            *pixel = self.map(*pixel);
+        }
+        Some(recoloured)
+    }
+
+    /// Moves a pixel of the asset map from grey and white to this theme's colours.
+    fn map(&self, pixel: Rgba<u8>) -> Rgba<u8> {
+        let [r, g, b, a] = pixel.0;
+        let light = r.max(g).max(b).max(ATLAS_INK);
+        let t = (light - ATLAS_INK) as f32 / (255 - ATLAS_INK) as f32;
+        let mix = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * t).round() as u8;
+        let (fg, bg) = (self.foreground, self.background);
+        Rgba([
+            mix(fg.r(), bg.r()),
+            mix(fg.g(), bg.g()),
+            mix(fg.b(), bg.b()),
+            a,
+        ])
+    }
+}
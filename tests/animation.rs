@@ -0,0 +1,73 @@
+//! Checks that clips play from their tables and that a run picks the right one.
+
+use dino::animation::{self, Animator};
+use dino::input::Input;
+use dino::mode::GameMode;
+use dino::run::Run;
+use dino::settings::Settings;
+use dino::sprite;
+
+#[test]
+fn clips_are_found_by_name() {
+    for name in ["run", "duck-run", "jump", "dead", "bird-flap", "blink-idle"] {
+        assert_eq!(animation::clip(name).unwrap().name, name);
+    }
+    assert!(animation::clip("moonwalk").is_none());
+}
+
+#[test]
+fn looping_clips_start_again_and_others_hold() {
+    let mut run = Animator::new(&animation::RUN);
+    let mut jump = Animator::new(&animation::JUMP);
+    for _ in 0..14 {
+        run.advance(1.0);
+        jump.advance(1.0);
+    }
+    assert_eq!(run.sprite(), sprite::DINO_RIGHT);
+    assert_eq!(jump.sprite(), sprite::DINO_STILL);
+
+    for _ in 0..7 {
+        run.advance(1.0);
+    }
+    assert_eq!(run.sprite(), sprite::DINO_LEFT);
+}
+
+#[test]
+fn faster_playback_changes_frames_sooner() {
+    let mut normal = Animator::new(&animation::RUN);
+    let mut fast = Animator::new(&animation::RUN);
+    for _ in 0..4 {
+        normal.advance(1.0);
+        fast.advance(2.0);
+    }
+    assert_eq!(normal.sprite(), sprite::DINO_RIGHT);
+    assert_eq!(fast.sprite(), sprite::DINO_LEFT);
+}
+
+#[test]
+fn runs_jump_then_run_then_die() {
+    let settings = Settings::default();
+    let mut run = Run::new(0);
+    assert_eq!(run.animator().clip().name, "blink-idle");
+
+    let jump = Input {
+        jump: true,
+        duck: false,
+    };
+    // the dino leaves the floor the tick after the press
+    run.step(jump, &settings, GameMode::Classic).unwrap();
+    run.step(jump, &settings, GameMode::Classic).unwrap();
+    assert_eq!(run.animator().clip().name, "jump");
+
+    while !run.on_ground() {
+        run.step(Input::default(), &settings, GameMode::Classic)
+            .unwrap();
+    }
+    assert_eq!(run.animator().clip().name, "run");
+
+    while !run.is_finished() {
+        run.step(Input::default(), &settings, GameMode::Classic)
+            .unwrap();
+    }
+    assert_eq!(run.animator().sprite(), sprite::DINO_DEAD);
+}
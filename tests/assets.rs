@@ -0,0 +1,115 @@
+//! Checks that the game starts whatever it finds in storage and on disk.
+
+use dino::assets::{self, Atlas};
+use dino::mode::GameMode;
+use dino::save::{SaveData, SAVE_KEY};
+use dino::DinoGame;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Storage holding whatever the test puts in it.
+#[derive(Default)]
+struct Memory(HashMap<String, String>);
+
+impl eframe::Storage for Memory {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.0.get(key).cloned()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        self.0.insert(key.to_owned(), value);
+    }
+
+    fn flush(&mut self) {}
+}
+
+/// A path in the temp directory unique to this test.
+fn temp_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("dino-{}-{name}", std::process::id()))
+}
+
+#[test]
+fn the_built_in_asset_map_is_used_without_a_file() {
+    assert!(assets::load_override(&temp_file("missing.png"))
+        .unwrap()
+        .is_none());
+
+    let game = DinoGame::restore(None, Some(&temp_file("missing.png")));
+    assert_eq!(game.startup_problems().count(), 0);
+}
+
+#[test]
+fn asset_maps_on_disk_are_checked() {
+    let good = temp_file("good.png");
+    let built_in = Atlas::built_in();
+    built_in.image().save(&good).unwrap();
+    assert_eq!(
+        assets::load_override(&good).unwrap().as_ref(),
+        Some(built_in.image())
+    );
+
+    let small = temp_file("small.png");
+    image::RgbaImage::new(16, 16).save(&small).unwrap();
+    let err = assets::load_override(&small).unwrap_err();
+    assert!(format!("{err:#}").contains("16x16"), "{err:#}");
+
+    let broken = temp_file("broken.png");
+    std::fs::write(&broken, "not a png").unwrap();
+    assert!(assets::load_override(&broken).is_err());
+
+    // a bad asset map is reported, and the game starts with the built in one
+    let game = DinoGame::restore(None, Some(&broken));
+    assert_eq!(
+        game.startup_problems().collect::<Vec<_>>(),
+        ["startup-asset-map-failed"]
+    );
+
+    for file in [good, small, broken] {
+        std::fs::remove_file(file).unwrap();
+    }
+}
+
+#[test]
+fn screenshots_use_the_asset_map_on_disk() {
+    let mut red = Atlas::built_in().image().clone();
+    for pixel in red.pixels_mut() {
+        pixel.0[..3].copy_from_slice(&[255, 0, 0]);
+    }
+    let path = temp_file("red.png");
+    red.save(&path).unwrap();
+
+    let game = DinoGame::restore(None, Some(&path));
+    let screenshot = game.screenshot(0.25);
+    assert_ne!(screenshot, DinoGame::default().screenshot(0.25));
+    assert!(screenshot.pixels().any(|pixel| pixel.0 == [255, 0, 0, 255]));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn only_saved_progress_is_restored() {
+    let save = SaveData {
+        mode: GameMode::Zen,
+        best_scores: HashMap::from([(GameMode::Zen, 3400.0)]),
+        room: "office".to_owned(),
+        ..SaveData::default()
+    };
+    let mut storage = Memory::default();
+    eframe::Storage::set_string(&mut storage, SAVE_KEY, save.to_text().unwrap());
+
+    let game = DinoGame::restore(Some(&storage), None);
+    assert_eq!(game.startup_problems().count(), 0);
+    assert_eq!(game.save_data(), save);
+}
+
+#[test]
+fn a_damaged_save_starts_a_new_game() {
+    let mut storage = Memory::default();
+    eframe::Storage::set_string(&mut storage, SAVE_KEY, "(version: 2, mode: [".to_owned());
+
+    let game = DinoGame::restore(Some(&storage), None);
+    assert_eq!(
+        game.startup_problems().collect::<Vec<_>>(),
+        ["startup-save-failed"]
+    );
+    assert_eq!(game.save_data(), DinoGame::default().save_data());
+}
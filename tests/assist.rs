@@ -0,0 +1,221 @@
+//! Checks that each assist does what it says, and that assisted runs still play back.
+
+use dino::assist::{Assisted, Assists};
+use dino::controller::{Autopilot, Controller, Keyboard, View};
+use dino::input::Input;
+use dino::mode::{GameMode, RunHistory, RunResult, HISTORY_LENGTH};
+use dino::replay::Replay;
+use dino::run::Run;
+use dino::settings::Settings;
+use dino::DinoGame;
+
+const JUMP: Input = Input {
+    jump: true,
+    duck: false,
+};
+
+fn assisted(assists: Assists) -> Settings {
+    Settings {
+        assists,
+        ..Settings::default()
+    }
+}
+
+/// Presses the buttons it is given, one set per tick.
+#[derive(Clone)]
+struct Script(Vec<Input>);
+
+impl Controller for Script {
+    fn act(&mut self, _view: &View<'_>) -> Input {
+        if self.0.is_empty() {
+            Input::default()
+        } else {
+            self.0.remove(0)
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Script"
+    }
+}
+
+#[test]
+fn game_speed_slows_the_scroll() {
+    let distance = |game_speed| {
+        let settings = assisted(Assists {
+            game_speed,
+            ..Assists::default()
+        });
+        let mut run = Run::new(1);
+        for _ in 0..200 {
+            run.step(JUMP, &settings, GameMode::Zen).unwrap();
+        }
+        run.score()
+    };
+    let half = distance(50) / distance(100);
+    assert!((half - 0.5).abs() < 1e-9, "{half}");
+    assert_eq!(distance(10), distance(50));
+}
+
+/// Whether a jump pressed `early` ticks before landing still happens.
+fn buffered(extra: u32, early: u32) -> bool {
+    let settings = assisted(Assists {
+        jump_buffer: extra,
+        ..Assists::default()
+    });
+    let mut run = Run::new(2);
+    run.step(JUMP, &settings, GameMode::Zen).unwrap();
+    // find how long a jump lasts, then replay it pressing jump again before landing
+    let mut airtime = 0;
+    let mut probe = run.clone();
+    loop {
+        probe
+            .step(Input::default(), &settings, GameMode::Zen)
+            .unwrap();
+        airtime += 1;
+        if probe.on_ground() {
+            break;
+        }
+    }
+    for tick in 0..airtime {
+        let input = if tick == airtime - early {
+            JUMP
+        } else {
+            Input::default()
+        };
+        run.step(input, &settings, GameMode::Zen).unwrap();
+    }
+    run.step(Input::default(), &settings, GameMode::Zen)
+        .unwrap();
+    run.step(Input::default(), &settings, GameMode::Zen)
+        .unwrap();
+    !run.on_ground()
+}
+
+#[test]
+fn extra_jump_buffer_remembers_earlier_presses() {
+    let early = Settings::default().jump_buffer + 8;
+    assert!(!buffered(0, early));
+    assert!(buffered(12, early));
+}
+
+#[test]
+fn one_switch_jumps_then_drops() {
+    let presses = [JUMP, JUMP, Input::default(), JUMP, Input::default()];
+    let mut controller = Assisted::new(
+        Box::new(Script(presses.to_vec())),
+        &Assists {
+            one_switch: true,
+            ..Assists::default()
+        },
+    );
+    let mut run = Run::new(3);
+    let settings = Settings::default();
+    let mut chosen = Vec::new();
+    for _ in 0..presses.len() {
+        let input = controller.act(&View {
+            run: &run,
+            ui: None,
+        });
+        run.step(input, &settings, GameMode::Zen).unwrap();
+        chosen.push(input);
+    }
+    let duck = Input {
+        jump: false,
+        duck: true,
+    };
+    // held from the floor it jumps, pressed again in the air it drops
+    assert_eq!(
+        chosen,
+        [JUMP, JUMP, Input::default(), duck, Input::default()]
+    );
+    assert_eq!(controller.name(), "Script");
+    assert!(!controller.is_human());
+}
+
+#[test]
+fn auto_jump_clears_obstacles_without_input() {
+    let auto_jump = Assists {
+        auto_jump: true,
+        ..Assists::default()
+    };
+    for seed in 0..4 {
+        let mut game = DinoGame::simulation(seed);
+        game.set_controller(0, Assisted::new(Box::new(Script(Vec::new())), &auto_jump));
+        for tick in 0..2000 {
+            game.step_controllers(None).unwrap();
+            assert!(!game.is_dead(), "seed {seed} died on tick {tick}");
+        }
+    }
+
+    // left alone, the same player runs straight into the first obstacle
+    let mut game = DinoGame::simulation(0);
+    game.set_controller(0, Script(Vec::new()));
+    for _ in 0..2000 {
+        game.step_controllers(None).unwrap();
+    }
+    assert!(game.is_dead());
+}
+
+#[test]
+fn assists_wrap_people_and_stay_people() {
+    let assisted = Assisted::new(Box::new(Keyboard::default()), &Assists::default());
+    assert!(assisted.is_human());
+    assert!(!Autopilot::default().is_human());
+}
+
+#[test]
+fn assisted_replays_verify() {
+    let settings = assisted(Assists {
+        game_speed: 70,
+        jump_buffer: 5,
+        ..Assists::default()
+    });
+    let replay = Replay::record(
+        8,
+        GameMode::Classic,
+        settings,
+        &mut Autopilot::default(),
+        2000,
+    )
+    .unwrap();
+    let parsed = Replay::parse(&replay.to_text()).unwrap();
+    assert_eq!(parsed, replay);
+    parsed.verify().unwrap();
+}
+
+#[test]
+fn history_keeps_the_latest_runs() {
+    let mut history = RunHistory::default();
+    for run in 0..HISTORY_LENGTH + 5 {
+        history.record(RunResult {
+            mode: GameMode::Classic,
+            score: run as f64,
+            assisted: run % 2 == 0,
+        });
+    }
+    assert_eq!(history.runs.len(), HISTORY_LENGTH);
+    assert_eq!(history.runs[0].score, 5.0);
+    assert!(history.runs.last().unwrap().assisted);
+}
+
+#[test]
+fn forgiving_settings_count_as_assists() {
+    assert!(!Settings::default().assisted());
+    assert!(assisted(Assists {
+        one_switch: true,
+        ..Assists::default()
+    })
+    .assisted());
+    // the same timing help as an assist, just from the settings screen
+    assert!(Settings {
+        jump_buffer: 20,
+        ..Settings::default()
+    }
+    .assisted());
+    assert!(Settings {
+        coyote_time: 6,
+        ..Settings::default()
+    }
+    .assisted());
+}
@@ -0,0 +1,53 @@
+//! Checks the autopilot and that other bots can be plugged into the game.
+
+use dino::controller::{Autopilot, Controller, Keyboard, View};
+use dino::input::Input;
+use dino::DinoGame;
+
+#[test]
+fn autopilot_survives() {
+    for seed in 0..16 {
+        let mut game = DinoGame::simulation(seed);
+        game.set_controller(0, Autopilot::default());
+        for tick in 0..5000 {
+            game.step_controllers(None).unwrap();
+            assert!(!game.is_dead(), "seed {seed} died on tick {tick}");
+        }
+    }
+}
+
+/// A bot that never presses anything.
+#[derive(Clone)]
+struct Idle {
+    ticks: u32,
+}
+
+impl Controller for Idle {
+    fn act(&mut self, _view: &View<'_>) -> Input {
+        self.ticks += 1;
+        Input::default()
+    }
+
+    fn name(&self) -> &str {
+        "Idle"
+    }
+}
+
+#[test]
+fn custom_bots_are_consulted_each_tick() {
+    let mut game = DinoGame::race(5, 2);
+    game.set_controller(0, Autopilot::default());
+    game.set_controller(1, Idle { ticks: 0 });
+    while !game.runs()[1].is_finished() {
+        game.step_controllers(None).unwrap();
+    }
+    assert!(!game.runs()[0].is_finished());
+    assert!(game.runs()[0].score() == game.runs()[1].score());
+}
+
+#[test]
+fn bots_are_told_from_people() {
+    assert!(Keyboard::default().is_human());
+    assert!(!Autopilot::default().is_human());
+    assert!(!Idle { ticks: 0 }.is_human());
+}
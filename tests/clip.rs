@@ -0,0 +1,87 @@
+//! Draws clips of recorded runs.
+
+use dino::clip::{self, Frames, TICKS_PER_FRAME, TICKS_PER_SECOND};
+use dino::controller::Autopilot;
+use dino::input::Input;
+use dino::mode::GameMode;
+use dino::replay::Replay;
+use dino::settings::Settings;
+use image::codecs::gif::GifDecoder;
+use image::{AnimationDecoder, RgbaImage};
+use std::io::Cursor;
+
+fn replay(ticks: u32) -> Replay {
+    let mut replay = Replay::record(
+        1,
+        GameMode::Classic,
+        Settings::default(),
+        &mut Autopilot::default(),
+        ticks,
+    )
+    .unwrap();
+    // stand still at the end so the run dies
+    replay.inputs.extend([Input::default(); 600]);
+    replay.score = replay.play().unwrap().score();
+    replay
+}
+
+/// Every frame of a clip, kept around to compare.
+fn frames(replay: &Replay, seconds: Option<u32>, scale: f32) -> Vec<RgbaImage> {
+    let mut frames = Frames::new(replay, seconds, scale).unwrap();
+    let mut all = Vec::new();
+    while let Some(frame) = frames.draw_next().unwrap() {
+        all.push(frame.clone());
+    }
+    all
+}
+
+#[test]
+fn clips_cover_the_end_of_the_run() {
+    let replay = replay(600);
+    let whole = frames(&replay, None, 0.25);
+    let last = frames(&replay, Some(2), 0.25);
+    let per_second = (TICKS_PER_SECOND / TICKS_PER_FRAME) as usize;
+    assert!(whole.len() > last.len());
+    assert!((2 * per_second..=2 * per_second + 1).contains(&last.len()));
+    assert_eq!(whole.last(), last.last());
+    assert_ne!(last.first(), last.last());
+}
+
+#[test]
+fn long_runs_are_saved_whole() {
+    let replay = replay(70 * TICKS_PER_SECOND);
+    let per_second = (TICKS_PER_SECOND / TICKS_PER_FRAME) as usize;
+    let count = |seconds| {
+        let mut frames = Frames::new(&replay, seconds, 0.05).unwrap();
+        let mut count = 0;
+        while frames.draw_next().unwrap().is_some() {
+            count += 1;
+        }
+        count
+    };
+    assert_eq!(count(Some(65)), 65 * per_second + 1);
+    assert!(count(None) > 70 * per_second, "{}", count(None));
+}
+
+#[test]
+fn gifs_decode() {
+    let replay = replay(600);
+    let frames_drawn = frames(&replay, Some(1), 0.1);
+    let count = frames_drawn.len();
+    let (width, height) = frames_drawn[0].dimensions();
+
+    let gif = clip::encode_gif(Frames::new(&replay, Some(1), 0.1).unwrap()).unwrap();
+    let decoded = GifDecoder::new(Cursor::new(gif))
+        .unwrap()
+        .into_frames()
+        .collect_frames()
+        .unwrap();
+    assert_eq!(decoded.len(), count);
+    assert_eq!(decoded[0].buffer().dimensions(), (width, height));
+
+    let png = clip::encode_png(&frames_drawn[0]).unwrap();
+    assert_eq!(
+        image::load_from_memory(&png).unwrap().to_rgba8(),
+        frames_drawn[0]
+    );
+}
@@ -0,0 +1,49 @@
+//! Checks the timing of the crash animation.
+
+use dino::death::{DeathSequence, FREEZE_TICKS, RESTART_LOCKOUT_TICKS, SHAKE_TICKS};
+use epaint::Vec2;
+
+fn after(ticks: u32) -> DeathSequence {
+    let mut death = DeathSequence::default();
+    for _ in 0..ticks {
+        death.tick();
+    }
+    death
+}
+
+#[test]
+fn panel_shows_after_the_freeze() {
+    assert!(after(0).is_frozen());
+    assert_eq!(after(FREEZE_TICKS).panel(), 0.0);
+    assert!(!after(FREEZE_TICKS).is_frozen());
+    assert!(after(FREEZE_TICKS + 1).panel() > 0.0);
+    assert_eq!(after(100).panel(), 1.0);
+    assert!(!after(100).is_playing());
+}
+
+#[test]
+fn shake_dies_away() {
+    assert_ne!(after(1).shake(), Vec2::ZERO);
+    assert!(after(SHAKE_TICKS - 1).shake().length() < after(1).shake().length());
+    assert_eq!(after(SHAKE_TICKS).shake(), Vec2::ZERO);
+    assert_eq!(after(5).shake(), after(5).shake());
+}
+
+#[test]
+fn jumping_cant_restart_straight_away() {
+    assert!(!after(0).accepts_restart());
+    assert!(!after(RESTART_LOCKOUT_TICKS - 1).accepts_restart());
+    assert!(after(RESTART_LOCKOUT_TICKS).accepts_restart());
+}
+
+#[test]
+fn keeps_playing_until_jumping_restarts() {
+    for ticks in 0..=RESTART_LOCKOUT_TICKS {
+        let death = after(ticks);
+        assert_eq!(
+            death.is_playing(),
+            !death.accepts_restart(),
+            "after {ticks} ticks"
+        );
+    }
+}
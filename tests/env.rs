@@ -0,0 +1,76 @@
+//! Drives runs through the training environment.
+
+use dino::env::{Action, Env, FEATURES, NO_OBSTACLE};
+use dino::raster::{Raster, BACKGROUND};
+use dino::run::Run;
+
+fn play(env: &mut Env, seed: u64, ticks: usize) -> Vec<[f32; FEATURES]> {
+    let mut seen = vec![env.reset(seed).features];
+    for tick in 0..ticks {
+        let action = if tick % 40 == 0 {
+            Action::Jump
+        } else {
+            Action::Nothing
+        };
+        let (observation, reward, done) = env.step(action).unwrap();
+        assert!(reward >= 0.0);
+        seen.push(observation.features);
+        if done {
+            break;
+        }
+    }
+    seen
+}
+
+#[test]
+fn same_seed_same_episode() {
+    let mut env = Env::default();
+    assert_eq!(play(&mut env, 3, 2000), play(&mut env, 3, 2000));
+}
+
+#[test]
+fn standing_still_dies() {
+    let mut env = Env::default();
+    let first = env.reset(1);
+    assert_eq!(first.features[3], NO_OBSTACLE);
+    assert!(first.pixels.is_none());
+
+    let mut saw_obstacle = false;
+    for _ in 0..5000 {
+        let (observation, _, done) = env.step(Action::Nothing).unwrap();
+        if observation.features[3] < NO_OBSTACLE {
+            saw_obstacle = true;
+            let next = env.run().enemies().next().unwrap();
+            assert_eq!(observation.features[5], next.hitbox_height() as f32);
+            assert!(observation.features[5] > 10.0);
+        }
+        if done {
+            assert!(saw_obstacle);
+            return;
+        }
+    }
+    panic!("the dino never hit anything");
+}
+
+#[test]
+fn max_ticks_ends_the_episode() {
+    let mut env = Env::default().with_max_ticks(10);
+    env.reset(0);
+    let done: Vec<_> = (0..10)
+        .map(|_| env.step(Action::Nothing).unwrap().2)
+        .collect();
+    assert_eq!(done.iter().filter(|done| **done).count(), 1);
+    assert!(done[9]);
+}
+
+#[test]
+fn pixels_show_the_dino() {
+    let mut env = Env::default().with_pixels(0.25);
+    let pixels = env.reset(0).pixels.unwrap();
+    assert_eq!(pixels.dimensions(), Raster::new(0.25).image().dimensions());
+    assert!(pixels.pixels().any(|pixel| *pixel != BACKGROUND));
+
+    let mut raster = Raster::new(0.25);
+    raster.draw_run(&Run::new(0));
+    assert_eq!(*raster.image(), pixels);
+}
@@ -0,0 +1,91 @@
+//! Checks that the jump buffer and coyote time honour presses that are slightly off.
+
+use dino::input::Input;
+use dino::DinoGame;
+
+const JUMP: Input = Input {
+    jump: true,
+    duck: false,
+};
+
+/// A game with the dino standing on the ground, long before the first obstacle arrives.
+fn grounded(jump_buffer: u32, coyote_time: u32) -> DinoGame {
+    let mut game = DinoGame::simulation(0);
+    game.settings.jump_buffer = jump_buffer;
+    game.settings.coyote_time = coyote_time;
+    while !game.on_ground() {
+        game.step(Input::default()).unwrap();
+    }
+    game
+}
+
+/// Does a full jump, pressing jump again `early` ticks before landing, and returns whether the
+/// dino went straight back up.
+fn rejumps(jump_buffer: u32, early: usize) -> bool {
+    let mut game = grounded(jump_buffer, 0);
+    let mut arc = game.clone();
+    arc.step(JUMP).unwrap();
+    let mut ticks = 0;
+    loop {
+        let input = if arc.rising() { JUMP } else { Input::default() };
+        arc.step(input).unwrap();
+        ticks += 1;
+        if arc.on_ground() {
+            break;
+        }
+    }
+
+    game.step(JUMP).unwrap();
+    for tick in 1..=ticks {
+        let input = if game.rising() || tick == ticks - early {
+            JUMP
+        } else {
+            Input::default()
+        };
+        game.step(input).unwrap();
+    }
+    game.step(Input::default()).unwrap();
+    !game.on_ground()
+}
+
+#[test]
+fn presses_before_landing_are_buffered() {
+    assert!(rejumps(6, 0));
+    assert!(rejumps(6, 3));
+    assert!(rejumps(6, 6));
+    assert!(!rejumps(6, 7));
+    assert!(!rejumps(0, 1));
+}
+
+/// Runs until the first obstacle, pressing jump on `press`, and returns whether the dino gets
+/// past it.
+fn clears_first_obstacle(coyote_time: u32, press: usize) -> bool {
+    let mut game = grounded(0, coyote_time);
+    let mut jumped = false;
+    for tick in 0..1000 {
+        let input = if tick == press || (jumped && game.rising()) {
+            jumped = true;
+            JUMP
+        } else {
+            Input::default()
+        };
+        game.step(input).unwrap();
+        if game.is_dead() {
+            return false;
+        }
+        if jumped && game.on_ground() {
+            return true;
+        }
+    }
+    false
+}
+
+#[test]
+fn late_presses_are_forgiven() {
+    let latest = (0..1000)
+        .filter(|press| clears_first_obstacle(0, *press))
+        .max()
+        .expect("the first obstacle can be cleared");
+    assert!(!clears_first_obstacle(0, latest + 1));
+    assert!(clears_first_obstacle(3, latest + 1));
+}
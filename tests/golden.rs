@@ -0,0 +1,154 @@
+//! Draws sprites on the CPU and compares them with known good images in `tests/golden`.
+//!
+//! After changing how something is drawn on purpose, run the tests with `UPDATE_GOLDEN=1` to
+//! save the new images, and check them before committing.
+
+use dino::input::Input;
+use dino::raster::Raster;
+use dino::render::{self, RenderTarget, SIZE};
+use dino::sprite;
+use dino::theme::Theme;
+use dino::DinoGame;
+use egui::Color32;
+use image::RgbaImage;
+use std::path::PathBuf;
+
+/// Pixels per game unit, small enough to keep the images small.
+const SCALE: f32 = 0.5;
+
+/// Compares `image` with the golden image called `name`.
+fn check(name: &str, image: &RgbaImage) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.png"));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        image.save(&path).unwrap();
+        return;
+    }
+    let golden = image::open(&path)
+        .unwrap_or_else(|err| panic!("couldn't open {}: {err}", path.display()))
+        .to_rgba8();
+    if golden != *image {
+        let actual = std::env::temp_dir().join(format!("dino-{name}.png"));
+        image.save(&actual).unwrap();
+        panic!(
+            "{name} doesn't match {}, it was drawn as {}",
+            path.display(),
+            actual.display()
+        );
+    }
+}
+
+/// A point in game units, in the screen points render targets take.
+fn at(units: f64) -> f64 {
+    units * SIZE as f64
+}
+
+#[test]
+fn sprites() {
+    let mut raster = Raster::new(SCALE);
+    let sprites = [
+        sprite::DINO_REST,
+        sprite::DINO_LEFT,
+        sprite::DINO_RIGHT,
+        sprite::DINO_STILL,
+        sprite::CACTUS_SMALL,
+    ];
+    for (index, sprite) in sprites.iter().enumerate() {
+        let x = at(20.0 + 120.0 * index as f64);
+        raster.draw_sprite(sprite, x, at(160.0), SIZE, Color32::WHITE);
+    }
+    raster.draw_sprite(
+        &sprite::DINO_STILL,
+        at(620.0),
+        at(160.0),
+        SIZE,
+        Color32::from_rgba_premultiplied(90, 90, 90, 90),
+    );
+    check("sprites", raster.image());
+}
+
+#[test]
+fn floor_wraps_around() {
+    let floor = |distance| {
+        let mut raster = Raster::new(SCALE);
+        render::draw_floor(&mut raster, distance, 0.0, Color32::WHITE);
+        raster.into_image()
+    };
+    for distance in [0.0, 1000.0, 2390.0] {
+        check(&format!("floor-{distance}"), &floor(distance));
+    }
+    assert_eq!(floor(0.0), floor(2400.0));
+    assert_eq!(floor(1000.0), floor(4.0 * 2400.0 + 1000.0));
+}
+
+#[test]
+fn score_digits() {
+    let mut raster = Raster::new(SCALE);
+    render::draw_numbers(
+        &mut raster,
+        "0123456789",
+        at(20.0),
+        at(160.0),
+        false,
+        Color32::WHITE,
+    );
+    render::draw_numbers(&mut raster, "42", at(20.0), at(200.0), true, Color32::WHITE);
+    render::draw_numbers(
+        &mut raster,
+        "123456",
+        at(400.0),
+        at(200.0),
+        false,
+        Color32::WHITE,
+    );
+    check("digits", raster.image());
+}
+
+#[test]
+fn race_screenshot() {
+    let mut game = DinoGame::race(2, 2);
+    for tick in 0..400 {
+        let jump = Input {
+            jump: tick % 50 < 10,
+            duck: false,
+        };
+        game.step_players(&[jump, Input::default()]).unwrap();
+    }
+    check("race", &game.screenshot(SCALE));
+}
+
+#[test]
+fn game_over_panel() {
+    let mut game = DinoGame::simulation(4);
+    while !game.is_dead() {
+        game.step(Input::default()).unwrap();
+    }
+    let mut raster = Raster::new(SCALE);
+    raster.draw_run(&game.runs()[0]);
+    render::draw_game_over(&mut raster, 0.0, Color32::WHITE);
+    check("game-over", raster.image());
+}
+
+#[test]
+fn themes_recolour_the_field() {
+    let mut game = DinoGame::simulation(6);
+    for _ in 0..200 {
+        game.step(Input::default()).unwrap();
+    }
+    for (name, theme) in [
+        ("dark", Theme::DARK),
+        ("high-contrast", Theme::HIGH_CONTRAST),
+    ] {
+        let mut raster = Raster::new(SCALE);
+        raster.set_theme(theme);
+        render::draw_run(
+            &mut raster,
+            &game.runs()[0],
+            0.0,
+            &sprite::dino(&game.runs()[0]),
+            Color32::WHITE,
+        );
+        check(&format!("theme-{name}"), raster.image());
+    }
+}
@@ -0,0 +1,64 @@
+//! Checks the intro's phases, and that skipping it only changes what is drawn.
+
+use dino::input::Input;
+use dino::intro::{Intro, IntroPhase, FADE_TICKS, REVEAL_TICKS};
+use dino::mode::GameMode;
+use dino::run::Run;
+use dino::settings::Settings;
+
+#[test]
+fn phases_follow_the_first_jump() {
+    let mut intro = Intro::default();
+    for _ in 0..100 {
+        intro.tick(false);
+    }
+    assert_eq!(intro.phase(), IntroPhase::Idle);
+    assert_eq!(intro.reveal(), 0.0);
+
+    intro.tick(true);
+    assert_eq!(intro.phase(), IntroPhase::Reveal);
+    let mut last = intro.reveal();
+    for _ in 0..REVEAL_TICKS - 1 {
+        intro.tick(true);
+        assert!(intro.reveal() > last, "the wipe only moves forwards");
+        last = intro.reveal();
+    }
+    assert_eq!(intro.fade(), 0.0);
+
+    intro.tick(true);
+    assert_eq!(intro.phase(), IntroPhase::FadeIn);
+    assert_eq!(intro.reveal(), 1.0);
+    for _ in 0..FADE_TICKS {
+        intro.tick(true);
+    }
+    assert!(intro.is_done());
+    assert_eq!(intro.fade(), 1.0);
+}
+
+#[test]
+fn skipping_shows_everything() {
+    let mut intro = Intro::default();
+    intro.skip();
+    assert!(intro.is_done());
+    assert_eq!((intro.reveal(), intro.fade()), (1.0, 1.0));
+}
+
+#[test]
+fn skipping_doesnt_change_the_run() {
+    let settings = Settings::default();
+    let mut played = Run::new(3);
+    let mut skipped = Run::new(3);
+    skipped.skip_intro();
+
+    for tick in 0..600 {
+        let input = Input {
+            jump: tick % 40 < 10,
+            duck: false,
+        };
+        played.step(input, &settings, GameMode::Zen).unwrap();
+        skipped.step(input, &settings, GameMode::Zen).unwrap();
+    }
+    assert!(played.intro().is_done());
+    assert_eq!(played.score(), skipped.score());
+    assert_eq!(played.dino_y(), skipped.dino_y());
+}
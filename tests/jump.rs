@@ -0,0 +1,53 @@
+//! Checks that how long jump and duck are held changes the shape of a jump.
+
+use dino::input::Input;
+use dino::DinoGame;
+
+/// Lands the dino from the jump every simulation starts with.
+fn grounded() -> DinoGame {
+    let mut game = DinoGame::simulation(0);
+    while !game.on_ground() {
+        game.step(Input::default()).unwrap();
+    }
+    game
+}
+
+/// Jumps, holding the buttons given for each tick, and counts the ticks until the dino lands.
+fn airtime(hold: impl Fn(usize) -> Input) -> usize {
+    let mut game = grounded();
+    game.step(hold(0)).unwrap();
+    let mut ticks = 0;
+    loop {
+        ticks += 1;
+        game.step(hold(ticks)).unwrap();
+        if game.on_ground() {
+            return ticks;
+        }
+    }
+}
+
+fn jump(held: bool) -> Input {
+    Input {
+        jump: held,
+        duck: false,
+    }
+}
+
+#[test]
+fn holding_jump_jumps_higher() {
+    let tap = airtime(|tick| jump(tick == 0));
+    let half = airtime(|tick| jump(tick < 6));
+    let full = airtime(|_| jump(true));
+    assert!(tap < half, "{tap} >= {half}");
+    assert!(half < full, "{half} >= {full}");
+}
+
+#[test]
+fn ducking_fast_falls() {
+    let full = airtime(|_| jump(true));
+    let fast_fall = airtime(|tick| Input {
+        jump: true,
+        duck: tick > 10,
+    });
+    assert!(fast_fall < full, "{fast_fall} >= {full}");
+}
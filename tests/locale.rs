@@ -0,0 +1,136 @@
+//! Checks that every shipped language has every message the game shows.
+
+use dino::clip::ClipFormat;
+use dino::locale::{Language, Locale};
+use dino::mode::GameMode;
+use dino::particles::EffectsIntensity;
+use dino::theme::ThemeKind;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// The ids of the messages in a Fluent file. Messages start at the beginning of a line with a
+/// lowercase id, comments and continuation lines don't.
+fn message_ids(source: &str) -> BTreeSet<&str> {
+    source
+        .lines()
+        .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+        .filter_map(|line| line.split_once('=').map(|(id, _)| id.trim()))
+        .collect()
+}
+
+/// The message ids passed straight to the locale anywhere in `dir`.
+fn ids_in_source(dir: &Path, ids: &mut BTreeSet<String>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            ids_in_source(&path, ids);
+            continue;
+        }
+        if path.extension().map_or(true, |extension| extension != "rs") {
+            continue;
+        }
+        let source = std::fs::read_to_string(&path).unwrap();
+        for call in ["tr(\"", "tr_with(\"", "speak_with(\"", "report(\""] {
+            for (start, _) in source.match_indices(call) {
+                let rest = &source[start + call.len()..];
+                ids.insert(rest[..rest.find('"').unwrap()].to_owned());
+            }
+        }
+    }
+}
+
+#[test]
+fn every_language_has_every_message() {
+    let english = message_ids(Language::English.source());
+    assert!(english.len() > 50);
+    for language in Language::ALL {
+        let ids = message_ids(language.source());
+        let missing: Vec<_> = english.difference(&ids).collect();
+        let extra: Vec<_> = ids.difference(&english).collect();
+        assert!(
+            missing.is_empty(),
+            "{} is missing {missing:?}",
+            language.tag()
+        );
+        assert!(extra.is_empty(), "{} has unused {extra:?}", language.tag());
+
+        // a message that didn't parse would fall back to English
+        let locale = Locale::new(language);
+        let broken: Vec<_> = ids.iter().filter(|id| !locale.has(id)).collect();
+        assert!(
+            broken.is_empty(),
+            "{} can't show {broken:?}",
+            language.tag()
+        );
+    }
+}
+
+#[test]
+fn every_message_the_game_uses_exists() {
+    let english = message_ids(Language::English.source());
+    let mut used = BTreeSet::new();
+    ids_in_source(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+        &mut used,
+    );
+    assert!(used.len() > 50);
+    used.extend(GameMode::ALL.map(|mode| mode.message_id().to_owned()));
+    used.extend(GameMode::ALL.map(|mode| mode.description_id().to_owned()));
+    used.extend(ThemeKind::ALL.map(|theme| theme.message_id().to_owned()));
+    used.extend(EffectsIntensity::ALL.map(|effects| effects.message_id().to_owned()));
+    used.extend(ClipFormat::ALL.map(|format| format.message_id().to_owned()));
+    let missing: Vec<_> = used
+        .iter()
+        .filter(|id| !english.contains(id.as_str()))
+        .collect();
+    assert!(missing.is_empty(), "no English for {missing:?}");
+}
+
+#[test]
+fn system_locales_pick_the_closest_language() {
+    assert_eq!(Language::negotiate("de-AT"), Language::German);
+    assert_eq!(Language::negotiate("es"), Language::Spanish);
+    assert_eq!(Language::negotiate("he-IL"), Language::Hebrew);
+    assert_eq!(Language::negotiate("fr-FR"), Language::English);
+    assert_eq!(Language::negotiate("not a locale"), Language::English);
+    assert!(Language::Hebrew.is_rtl());
+    assert!(!Language::German.is_rtl());
+}
+
+#[test]
+fn right_to_left_text_is_drawn_in_display_order() {
+    let hebrew = Locale::new(Language::Hebrew);
+    let spoken = hebrew.speak_with("play", &[]);
+    assert_eq!(spoken, "שחק!");
+    // drawn left to right, the first letter has to end up on the right
+    let drawn: String = spoken.chars().rev().collect();
+    assert_eq!(hebrew.tr("play"), drawn);
+    // numbers still read left to right
+    assert!(hebrew
+        .tr_with("best", &[("score", 120.into())])
+        .contains("120"));
+
+    let spanish = Locale::new(Language::Spanish);
+    assert_eq!(spanish.tr_with("best", &[("score", 7.into())]), "Récord: 7");
+}
+
+#[test]
+fn every_language_can_be_drawn() {
+    let ctx = egui::Context::default();
+    dino::locale::install_fonts(&ctx);
+    // new fonts are only picked up at the start of a frame
+    let _ = ctx.run(egui::RawInput::default(), |_| {});
+    for language in Language::ALL {
+        let missing: BTreeSet<char> = ctx.fonts(|fonts| {
+            (language.source().chars())
+                .filter(|c| !c.is_control())
+                .filter(|c| !fonts.has_glyph(&egui::FontId::proportional(14.0), *c))
+                .collect()
+        });
+        assert!(
+            missing.is_empty(),
+            "{} can't draw {missing:?}",
+            language.tag()
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Checks the bookkeeping behind the daily challenge and best scores.
+
+use dino::mode::{daily_seed, format_day, DailyRecord};
+use dino::settings::Settings;
+
+#[test]
+fn days_format_as_dates() {
+    assert_eq!(format_day(0), "1970-01-01");
+    assert_eq!(format_day(11_016), "2000-02-29");
+    assert_eq!(format_day(20_745), "2026-10-19");
+}
+
+#[test]
+fn only_the_first_run_of_a_day_is_official() {
+    let mut daily = DailyRecord::default();
+    assert!(daily.start(100));
+    daily.finish(100, 50.0);
+    assert!(!daily.start(100));
+    assert!(daily.start(101));
+    daily.finish(101, 70.0);
+    assert_eq!(daily.result(100), Some(50.0));
+
+    let leaderboard: Vec<_> = daily.leaderboard().iter().map(|r| r.day).collect();
+    assert_eq!(leaderboard, [101, 100]);
+}
+
+#[test]
+fn an_abandoned_run_uses_up_the_day() {
+    let mut daily = DailyRecord::default();
+    assert!(daily.start(100));
+    // the game is closed before the run ends
+    assert_eq!(daily.result(100), Some(0.0));
+    assert!(!daily.start(100));
+}
+
+#[test]
+fn only_the_standard_rules_are_official() {
+    assert!(Settings::default().standard_rules());
+    // power-ups change the score as much as an assist does
+    assert!(!Settings {
+        power_ups: true,
+        ..Settings::default()
+    }
+    .standard_rules());
+    assert!(!Settings {
+        jump_buffer: 20,
+        ..Settings::default()
+    }
+    .standard_rules());
+}
+
+#[test]
+fn every_day_has_its_own_course() {
+    assert_ne!(daily_seed(100), daily_seed(101));
+}
@@ -0,0 +1,64 @@
+//! Checks what screen readers are told about a run.
+
+use dino::input::Input;
+use dino::locale::Locale;
+use dino::mode::GameMode;
+use dino::narration::Narrator;
+use dino::run::Run;
+use dino::settings::Settings;
+
+/// Plays a run with full jumps every so often, collecting everything announced.
+fn narrate(narrator: &mut Narrator, ticks: u32, mode: GameMode) -> Vec<String> {
+    let settings = Settings::default();
+    let locale = Locale::default();
+    let mut run = Run::new(6);
+    let mut said = Vec::new();
+    for tick in 0..ticks {
+        if run.is_finished() {
+            break;
+        }
+        let input = Input {
+            jump: tick % 40 < 10,
+            duck: false,
+        };
+        run.step(input, &settings, mode).unwrap();
+        said.extend(narrator.update(&run, &locale));
+    }
+    said.extend(narrator.update(&run, &locale));
+    said
+}
+
+#[test]
+fn milestones_and_high_scores_are_announced_once() {
+    let mut narrator = Narrator::new(150.0 * 85.0, false);
+    let said = narrate(&mut narrator, 4000, GameMode::Zen);
+    assert!(said.contains(&"100 points.".to_owned()));
+    assert!(said.contains(&"200 points.".to_owned()));
+    let beaten = said.iter().filter(|line| *line == "New high score!");
+    assert_eq!(beaten.count(), 1);
+    assert!(!said.iter().any(|line| line.contains("cactus")));
+}
+
+#[test]
+fn death_is_announced_with_the_score() {
+    let mut narrator = Narrator::new(0.0, false);
+    let said = narrate(&mut narrator, 100_000, GameMode::Classic);
+    let last = said.last().unwrap();
+    assert!(last.starts_with("Game over. Score "), "{last}");
+    assert_eq!(
+        said.iter()
+            .filter(|line| line.starts_with("Game over"))
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn each_obstacle_is_announced_once() {
+    let mut narrator = Narrator::new(0.0, true);
+    let said = narrate(&mut narrator, 1500, GameMode::Zen);
+    let obstacles: Vec<_> = said.iter().filter(|line| line.contains("cactus")).collect();
+    assert!(obstacles.len() > 3);
+    assert!(obstacles.len() < 100, "announced {} times", obstacles.len());
+    assert!(obstacles.iter().all(|line| line.ends_with(" seconds.")));
+}
@@ -0,0 +1,179 @@
+//! Races two clients against each other through a relay on localhost.
+
+use dino::input::Input;
+use dino::net::{self, relay, ClientMessage, Event, Lockstep, RelayMessage, Session};
+use dino::DinoGame;
+use std::net::TcpListener;
+use std::time::{Duration, Instant};
+
+const JUMP: Input = Input {
+    jump: true,
+    duck: false,
+};
+
+#[test]
+fn messages_round_trip() {
+    let client = [
+        ClientMessage::Join {
+            room: "room".to_owned(),
+        },
+        ClientMessage::Input {
+            tick: 12,
+            input: JUMP,
+        },
+    ];
+    for message in client {
+        assert_eq!(ClientMessage::parse(&message.to_text()).unwrap(), message);
+    }
+
+    let relay = [
+        RelayMessage::Start {
+            seed: u64::MAX,
+            player: 1,
+            players: 2,
+        },
+        RelayMessage::Input {
+            player: 0,
+            tick: 3,
+            input: Input {
+                jump: false,
+                duck: true,
+            },
+        },
+        RelayMessage::Left { player: 1 },
+    ];
+    for message in relay {
+        assert_eq!(RelayMessage::parse(&message.to_text()).unwrap(), message);
+    }
+    assert!(RelayMessage::parse("start 1").is_err());
+}
+
+#[test]
+fn rooms_need_a_name() {
+    assert_eq!(net::room_problem("dino"), None);
+    assert_eq!(net::room_problem(""), Some("room-empty"));
+    assert_eq!(net::room_problem("  "), Some("room-empty"));
+    assert!(dino::locale::Locale::default().has("room-empty"));
+}
+
+#[test]
+fn lockstep_waits_for_every_player() {
+    let mut lockstep = Lockstep::new(0, 2, 3);
+    let scheduled = lockstep.schedule(JUMP);
+    assert_eq!(scheduled.len(), 4);
+    assert_eq!(scheduled[3], (3, JUMP));
+    assert_eq!(lockstep.advance(), None);
+
+    lockstep.receive(1, 0, JUMP);
+    assert_eq!(lockstep.advance(), Some(vec![Input::default(), JUMP]));
+    assert_eq!(lockstep.advance(), None);
+
+    // running ahead of the other player is capped by the delay
+    assert_eq!(lockstep.schedule(JUMP).len(), 1);
+    assert!(lockstep.schedule(JUMP).is_empty());
+
+    lockstep.leave(1);
+    assert_eq!(lockstep.advance(), Some(vec![Input::default(); 2]));
+}
+
+/// What each player does: the first keeps doing full jumps, the second never jumps.
+fn play(player: usize, game: &DinoGame) -> Input {
+    let run = &game.runs()[player];
+    if player == 0 && (run.on_ground() || run.rising()) {
+        JUMP
+    } else {
+        Input::default()
+    }
+}
+
+#[test]
+fn clients_simulate_the_same_race() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("ws://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || relay::serve(listener));
+
+    let mut sessions = [
+        Session::connect(&address, "test", 2),
+        Session::connect(&address, "test", 5),
+    ];
+    let mut games: [Option<DinoGame>; 2] = [None, None];
+    let deadline = Instant::now() + Duration::from_secs(30);
+
+    while games
+        .iter()
+        .any(|game| !game.as_ref().is_some_and(DinoGame::is_dead))
+    {
+        assert!(Instant::now() < deadline, "the race didn't finish in time");
+        for (session, game) in sessions.iter_mut().zip(&mut games) {
+            for event in session.poll() {
+                match event {
+                    Event::Start { seed, players, .. } => {
+                        *game = Some(DinoGame::race(seed, players));
+                    }
+                    event => panic!("unexpected {event:?}"),
+                }
+            }
+            let (Some(player), Some(game)) = (session.player(), game) else {
+                continue;
+            };
+            let input = play(player, game);
+            for inputs in session.tick(input) {
+                if !game.is_dead() {
+                    game.step_players(&inputs).unwrap();
+                }
+            }
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    let [Some(first), Some(second)] = &games else {
+        unreachable!();
+    };
+    let scores = |game: &DinoGame| {
+        game.runs()
+            .iter()
+            .map(|run| run.score())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(scores(first), scores(second));
+    assert_ne!(sessions[0].player(), sessions[1].player());
+}
+
+#[test]
+fn a_bad_start_from_the_relay_disconnects() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("ws://{}", listener.local_addr().unwrap());
+    // a relay that seats the player outside the race
+    std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut socket = tungstenite::accept(stream).unwrap();
+        socket.read().unwrap();
+        let start = RelayMessage::Start {
+            seed: 1,
+            player: 2,
+            players: 2,
+        };
+        socket
+            .send(tungstenite::Message::text(start.to_text()))
+            .unwrap();
+        // wait for the client to hang up
+        while socket.read().is_ok() {}
+    });
+
+    let mut session = Session::connect(&address, "test", 2);
+    let deadline = Instant::now() + Duration::from_secs(30);
+    let events = loop {
+        assert!(Instant::now() < deadline, "the relay's start never arrived");
+        let events = session.poll();
+        if !events.is_empty() {
+            break events;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    };
+    assert!(
+        matches!(&events[..], [Event::Disconnected(reason)] if reason.contains("player 3 of 2")),
+        "{events:?}"
+    );
+    assert_eq!(session.player(), None);
+    assert!(session.tick(JUMP).is_empty());
+}
@@ -0,0 +1,76 @@
+//! Checks the particle pool, and that effects never change how a run plays.
+
+use dino::input::Input;
+use dino::mode::GameMode;
+use dino::particles::{EffectsIntensity, ParticleKind, Particles, MAX_PARTICLES};
+use dino::run::Run;
+use dino::settings::Settings;
+
+fn burst(particles: &mut Particles, count: u32, intensity: EffectsIntensity) {
+    particles.emit(
+        ParticleKind::Dust,
+        count,
+        intensity,
+        (0.0, 0.0),
+        (0.0, -1.0),
+        1.0,
+    );
+}
+
+#[test]
+fn intensity_scales_how_many_are_made() {
+    for (intensity, made) in [
+        (EffectsIntensity::Off, 0),
+        (EffectsIntensity::Low, 4),
+        (EffectsIntensity::Full, 10),
+    ] {
+        let mut particles = Particles::new(0);
+        burst(&mut particles, 10, intensity);
+        assert_eq!(particles.iter().count(), made, "{intensity:?}");
+    }
+}
+
+#[test]
+fn pool_is_capped_and_reused() {
+    let mut particles = Particles::new(0);
+    burst(
+        &mut particles,
+        MAX_PARTICLES as u32 * 2,
+        EffectsIntensity::Full,
+    );
+    assert_eq!(particles.iter().count(), MAX_PARTICLES);
+
+    for _ in 0..100 {
+        particles.tick(0.0);
+    }
+    assert_eq!(particles.iter().count(), 0);
+
+    burst(&mut particles, 5, EffectsIntensity::Full);
+    assert_eq!(particles.iter().count(), 5);
+}
+
+#[test]
+fn effects_dont_change_the_run() {
+    let run = |effects| {
+        let settings = Settings {
+            effects,
+            ..Settings::default()
+        };
+        let mut run = Run::new(9);
+        let mut tick = 0;
+        while !run.is_finished() && tick < 3000 {
+            let input = Input {
+                jump: tick % 35 < 8,
+                duck: false,
+            };
+            run.step(input, &settings, GameMode::Classic).unwrap();
+            tick += 1;
+        }
+        run
+    };
+    let full = run(EffectsIntensity::Full);
+    let off = run(EffectsIntensity::Off);
+    assert!(full.particles().iter().count() > 0);
+    assert_eq!(off.particles().iter().count(), 0);
+    assert_eq!(full.score(), off.score());
+}
@@ -0,0 +1,230 @@
+//! Checks that players sharing the game keep their progress apart.
+
+use dino::locale::Locale;
+use dino::mode::GameMode;
+use dino::profile::{Profiles, PROFILES_KEY};
+use dino::save::{SaveData, SAVE_KEY};
+use dino::DinoGame;
+use eframe::{App, Storage};
+use std::collections::HashMap;
+
+/// Storage holding whatever the test puts in it.
+#[derive(Default)]
+struct Memory(HashMap<String, String>);
+
+impl Storage for Memory {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.0.get(key).cloned()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        self.0.insert(key.to_owned(), value);
+    }
+
+    fn flush(&mut self) {}
+}
+
+fn best(score: f64) -> SaveData {
+    SaveData {
+        best_scores: HashMap::from([(GameMode::Classic, score)]),
+        ..SaveData::default()
+    }
+}
+
+#[test]
+fn names_have_to_be_unique_and_not_empty() {
+    let mut profiles = Profiles::new("Ana".to_owned(), SaveData::default());
+    profiles.create(" Ben ").unwrap();
+    assert_eq!(profiles.current().name, "Ben");
+
+    assert_eq!(
+        profiles.name_problem("  ", None),
+        Some("profile-name-empty")
+    );
+    assert_eq!(
+        profiles.name_problem("ana", None),
+        Some("profile-name-taken")
+    );
+    assert_eq!(
+        profiles.name_problem(&"a".repeat(100), None),
+        Some("profile-name-too-long")
+    );
+    assert!(profiles.create("ANA").is_err());
+
+    // a profile can keep its own name
+    assert_eq!(profiles.name_problem("ben", Some(1)), None);
+    profiles.rename(1, "Bea").unwrap();
+    assert!(profiles.rename(0, "Bea").is_err());
+
+    let locale = Locale::default();
+    for id in [
+        "profile-name-empty",
+        "profile-name-too-long",
+        "profile-name-taken",
+    ] {
+        assert!(locale.has(id), "{id}");
+    }
+}
+
+#[test]
+fn the_last_profile_stays() {
+    let mut profiles = Profiles::new("Ana".to_owned(), SaveData::default());
+    assert!(profiles.delete(0).is_err());
+
+    profiles.create("Ben").unwrap();
+    profiles.create("Cat").unwrap();
+    profiles.select(1);
+    profiles.delete(0).unwrap();
+    assert_eq!(profiles.current().name, "Ben");
+    profiles.delete(0).unwrap();
+    assert_eq!(profiles.current().name, "Cat");
+    assert_eq!(profiles.all().len(), 1);
+}
+
+#[test]
+fn profiles_are_stored_and_deleted_ones_wiped() {
+    let mut profiles = Profiles::new("Ana".to_owned(), best(100.0));
+    profiles.create("Ben").unwrap();
+    profiles.current_mut().save = best(200.0);
+    profiles.create("Cat").unwrap();
+    profiles.select(1);
+
+    let mut storage = Memory::default();
+    profiles.store(&mut storage).unwrap();
+    let mut loaded = Profiles::load(&storage, String::new());
+    assert_eq!(loaded, profiles);
+
+    let cat = storage.0.clone();
+    loaded.delete(2).unwrap();
+    loaded.store(&mut storage).unwrap();
+    let changed: Vec<_> = (storage.0.iter())
+        .filter(|(key, value)| cat[*key] != **value)
+        .map(|(key, _)| key.as_str())
+        .collect();
+    assert_eq!(changed.len(), 2, "{changed:?}");
+    assert!(changed.contains(&PROFILES_KEY));
+    assert!(storage.0.values().any(String::is_empty));
+
+    let reloaded = Profiles::load(&storage, String::new());
+    assert_eq!(reloaded.all().len(), 2);
+    assert_eq!(reloaded.current().name, "Ben");
+}
+
+#[test]
+fn progress_from_before_profiles_becomes_the_first_profile() {
+    let mut storage = Memory::default();
+    storage.set_string(SAVE_KEY, best(300.0).to_text().unwrap());
+
+    let profiles = Profiles::load(&storage, "Player 1".to_owned());
+    assert_eq!(profiles.all().len(), 1);
+    assert_eq!(profiles.current().name, "Player 1");
+    assert_eq!(profiles.current().save, best(300.0));
+}
+
+#[test]
+fn switching_profiles_swaps_progress() {
+    let mut game = DinoGame::default();
+    game.load_save(best(100.0));
+    game.create_profile("Ben").unwrap();
+    assert_eq!(game.save_data(), DinoGame::default().save_data());
+
+    game.load_save(best(200.0));
+    game.select_profile(0);
+    assert_eq!(game.save_data().best_scores, best(100.0).best_scores);
+
+    let mut storage = Memory::default();
+    game.save(&mut storage);
+    let game = DinoGame::restore(Some(&storage), None);
+    assert_eq!(game.profiles().all().len(), 2);
+    assert_eq!(game.save_data().best_scores, best(100.0).best_scores);
+    assert_eq!(
+        game.profiles().all()[1].save.best_scores,
+        best(200.0).best_scores
+    );
+}
+
+/// Three stored profiles, with the second one's save damaged.
+fn damaged_storage() -> Memory {
+    let mut profiles = Profiles::new("Ana".to_owned(), best(100.0));
+    profiles.create("Ben").unwrap();
+    profiles.create("Cat").unwrap();
+    profiles.current_mut().save = best(300.0);
+    profiles.select(1);
+    let mut storage = Memory::default();
+    profiles.store(&mut storage).unwrap();
+    storage.set_string("save-1", "(version: 2, best_scores: {".to_owned());
+    storage
+}
+
+#[test]
+fn a_damaged_profile_is_left_alone() {
+    let mut storage = damaged_storage();
+    let mut profiles = Profiles::load(&storage, String::new());
+    let names: Vec<_> = profiles.all().iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["Ana", "Ben", "Cat"]);
+    assert!(profiles.all()[1].damaged().unwrap().contains("Ben"));
+    assert_eq!(profiles.all()[2].save, best(300.0));
+
+    // Ben was selected, but can't be played until his save is started over
+    assert_eq!(profiles.current().name, "Ana");
+    profiles.select(1);
+    assert_eq!(profiles.current().name, "Ana");
+
+    let damaged = storage.0["save-1"].clone();
+    profiles.store(&mut storage).unwrap();
+    assert_eq!(storage.0["save-1"], damaged);
+
+    profiles.start_over(1);
+    profiles.select(1);
+    profiles.store(&mut storage).unwrap();
+    let profiles = Profiles::load(&storage, String::new());
+    assert_eq!(profiles.current().name, "Ben");
+    assert!(profiles
+        .all()
+        .iter()
+        .all(|profile| profile.damaged().is_none()));
+}
+
+#[test]
+fn a_damaged_list_of_profiles_stops_saving() {
+    let mut storage = damaged_storage();
+    storage.set_string(PROFILES_KEY, "[".to_owned());
+    let before = storage.0.clone();
+
+    let mut profiles = Profiles::load(&storage, "Player 1".to_owned());
+    assert!(profiles.damaged().is_some());
+    assert_eq!(profiles.current().name, "Player 1");
+    profiles.store(&mut storage).unwrap();
+    assert_eq!(storage.0, before);
+
+    profiles.start_list_over();
+    profiles.store(&mut storage).unwrap();
+    assert!(Profiles::load(&storage, String::new()).damaged().is_none());
+}
+
+#[test]
+fn the_game_keeps_damaged_progress_through_saving() {
+    let mut storage = damaged_storage();
+    storage.set_string("save-2", "not a save".to_owned());
+    storage.set_string(SAVE_KEY, "not a save either".to_owned());
+    let before = storage.0.clone();
+
+    // with nothing left to play, a new profile is added to play with
+    let mut game = DinoGame::restore(Some(&storage), None);
+    assert_eq!(
+        game.startup_problems().collect::<Vec<_>>(),
+        ["startup-save-failed"; 3]
+    );
+    assert_eq!(game.profiles().all().len(), 4);
+    assert_eq!(game.profiles().current().name, "Player 1");
+
+    game.load_save(best(50.0));
+    game.save(&mut storage);
+    for (key, text) in before {
+        if key.starts_with(SAVE_KEY) {
+            assert_eq!(storage.0[&key], text, "{key}");
+        }
+    }
+    let profiles = Profiles::load(&storage, String::new());
+    assert_eq!(profiles.current().save.best_scores, best(50.0).best_scores);
+}
@@ -0,0 +1,58 @@
+//! Checks that two player races share a course and only end once both dinos are out.
+
+use dino::input::Input;
+use dino::DinoGame;
+
+const JUMP: Input = Input {
+    jump: true,
+    duck: false,
+};
+
+#[test]
+fn same_inputs_give_the_same_run() {
+    let mut game = DinoGame::race(7, 2);
+    for tick in 0..5000 {
+        let input = if tick % 40 < 20 {
+            JUMP
+        } else {
+            Input::default()
+        };
+        game.step_players(&[input, input]).unwrap();
+        let [first, second] = game.runs() else {
+            panic!("a race has two runs");
+        };
+        assert_eq!(first.score(), second.score());
+        assert_eq!(first.is_finished(), second.is_finished());
+        if game.is_dead() {
+            return;
+        }
+    }
+    panic!("nobody jumping on a timer survives this long");
+}
+
+#[test]
+fn race_ends_when_both_players_are_out() {
+    let mut game = DinoGame::race(3, 2);
+    let mut jumper_outlived_idler = false;
+    for _ in 0..5000 {
+        // the first player keeps doing full jumps, the second never jumps
+        let first = &game.runs()[0];
+        let input = if first.on_ground() || first.rising() {
+            JUMP
+        } else {
+            Input::default()
+        };
+        game.step_players(&[input]).unwrap();
+        let runs = game.runs();
+        assert_eq!(game.is_dead(), runs.iter().all(|run| run.is_finished()));
+        if runs[1].is_finished() && !runs[0].is_finished() {
+            jumper_outlived_idler = true;
+        }
+        if game.is_dead() {
+            break;
+        }
+    }
+    assert!(game.is_dead());
+    assert!(jumper_outlived_idler);
+    assert!(game.runs()[0].score() > game.runs()[1].score());
+}
@@ -0,0 +1,176 @@
+//! Collects sprites into the renderer's display list and batches them.
+
+use dino::batch::{SpriteBatch, Transform};
+use dino::input::Input;
+use dino::mode::GameMode;
+use dino::raster::{Raster, FIELD_TOP};
+use dino::render::{self, RenderTarget, Renderer, SIZE};
+use dino::run::Run;
+use dino::settings::Settings;
+use dino::sprite;
+use dino::DinoGame;
+use egui::{pos2, vec2, Color32, Rect, TextureId};
+
+#[test]
+fn runs_draw_into_one_display_list() {
+    let mut game = DinoGame::simulation(5);
+    for _ in 0..300 {
+        game.step(Default::default()).unwrap();
+    }
+    let run = &game.runs()[0];
+
+    let mut renderer = Renderer::default();
+    render::draw_run(&mut renderer, run, 0.0, &sprite::DINO_STILL, Color32::WHITE);
+    let list = renderer.display_list();
+
+    // four score digits, the obstacles, the dino, two floors and the particles
+    let particles = run.particles().iter().count();
+    assert_eq!(list.len(), 4 + run.enemies().count() + 1 + 2 + particles);
+    let dino = list
+        .iter()
+        .find(|draw| draw.sprite == sprite::DINO_STILL)
+        .unwrap();
+    assert_eq!(dino.rect.min.x, 30.0 * SIZE);
+    assert_eq!(dino.rect.size(), sprite::DINO_STILL.size * SIZE);
+    assert!(list[..4]
+        .iter()
+        .all(|draw| (0..10).any(|digit| sprite::digit(digit) == Some(draw.sprite))));
+}
+
+#[test]
+fn batches_turn_and_mirror_sprites() {
+    let mut batch = SpriteBatch::new(TextureId::default());
+    let rect = Rect::from_min_size(pos2(10.0, 20.0), vec2(40.0, 20.0));
+    let uv = Rect::from_min_max(pos2(0.0, 0.0), pos2(0.5, 1.0));
+    batch.push(rect, uv, Color32::WHITE, Transform::default());
+    batch.push(
+        rect,
+        uv,
+        Color32::RED,
+        Transform {
+            rotation: std::f32::consts::FRAC_PI_2,
+            flip_x: true,
+            flip_y: false,
+        },
+    );
+    assert_eq!(batch.len(), 2);
+
+    let vertices = &batch.mesh().vertices;
+    assert_eq!(batch.mesh().indices.len(), 12);
+    assert_eq!(vertices[0].pos, rect.left_top());
+    assert_eq!(vertices[0].uv, uv.left_top());
+    // mirrored, the top left corner shows the right of the sprite
+    assert_eq!(vertices[4].uv, uv.right_top());
+    assert_eq!(vertices[4].color, Color32::RED);
+    // a quarter turn clockwise moves the top left corner to the top right
+    let turned = vertices[4].pos;
+    assert!((turned.x - 40.0).abs() < 1e-4 && (turned.y - 10.0).abs() < 1e-4);
+}
+
+#[test]
+fn raster_turns_and_mirrors_sprites() {
+    // one pixel per screen point, so the sprite covers 70 by 72 pixels from 100, 10
+    let draw = |transform| {
+        let mut raster = Raster::new(SIZE);
+        let y = FIELD_TOP * SIZE as f64 + 10.0;
+        raster.draw_transformed(
+            &sprite::DINO_LEFT,
+            100.0,
+            y,
+            SIZE,
+            Color32::WHITE,
+            transform,
+        );
+        raster.into_image()
+    };
+    let upright = draw(Transform::default());
+    let mirrored = draw(Transform {
+        flip_x: true,
+        ..Transform::default()
+    });
+    assert_ne!(upright, mirrored);
+    for y in 10..82 {
+        for x in 0..70 {
+            assert_eq!(
+                upright.get_pixel(100 + x, y),
+                mirrored.get_pixel(169 - x, y),
+                "{x}, {y}"
+            );
+        }
+    }
+
+    let half_turn = draw(Transform {
+        rotation: std::f32::consts::PI,
+        ..Transform::default()
+    });
+    let flipped_both = draw(Transform {
+        flip_x: true,
+        flip_y: true,
+        ..Transform::default()
+    });
+    let differing = half_turn
+        .pixels()
+        .zip(flipped_both.pixels())
+        .filter(|(a, b)| a != b)
+        .count();
+    // a half turn is both flips, give or take sampling on the edges
+    assert!(differing < 40, "{differing} pixels differ");
+}
+
+/// Where each power-up badge in a run is drawn.
+fn badges(run: &Run) -> Vec<Rect> {
+    let mut renderer = Renderer::default();
+    render::draw_run(&mut renderer, run, 0.0, &sprite::DINO_STILL, Color32::WHITE);
+    let kinds = [
+        sprite::SHIELD,
+        sprite::SLOW_MOTION,
+        sprite::SCORE_MULTIPLIER,
+    ];
+    (renderer.display_list().iter())
+        .filter(|draw| kinds.contains(&draw.sprite))
+        .map(|draw| draw.rect)
+        .collect()
+}
+
+/// Whether a power-up's timer has shown up above the field.
+fn collected(run: &Run) -> bool {
+    badges(run).iter().any(|rect| rect.min.y < 250.0 * SIZE)
+}
+
+#[test]
+fn power_ups_are_drawn_and_only_collected_within_reach() {
+    let settings = Settings {
+        power_ups: true,
+        ..Settings::default()
+    };
+    let jump = Input {
+        jump: true,
+        duck: false,
+    };
+    let mut run = Run::new(3);
+    run.skip_intro();
+    run.step(jump, &settings, GameMode::Zen).unwrap();
+    // wait for a power-up to come on screen
+    while badges(&run).is_empty() {
+        run.step(Input::default(), &settings, GameMode::Zen)
+            .unwrap();
+    }
+    assert!(!collected(&run));
+
+    // whether the power-up is collected jumping on `press`, holding jump for as high a jump
+    // as there is
+    let collects = |press: Option<u32>| {
+        let mut run = run.clone();
+        for tick in 0..600 {
+            let held = press.is_some_and(|press| (press..press + 30).contains(&tick));
+            let input = if held { jump } else { Input::default() };
+            run.step(input, &settings, GameMode::Zen).unwrap();
+            if collected(&run) {
+                return true;
+            }
+        }
+        false
+    };
+    assert!(collects(None));
+    assert!((0..600).any(|press| !collects(Some(press))));
+}
@@ -0,0 +1,62 @@
+//! Records runs and plays them back.
+
+use dino::controller::Autopilot;
+use dino::input::Input;
+use dino::mode::GameMode;
+use dino::replay::Replay;
+use dino::settings::Settings;
+use dino::DinoGame;
+
+#[test]
+fn recorded_runs_verify() {
+    let replay = Replay::record(
+        4,
+        GameMode::Classic,
+        Settings::default(),
+        &mut Autopilot::default(),
+        3000,
+    )
+    .unwrap();
+    assert_eq!(replay.inputs.len(), 3000);
+    assert_eq!(replay.verify().unwrap().score(), replay.score);
+
+    let parsed = Replay::parse(&replay.to_text()).unwrap();
+    assert_eq!(parsed, replay);
+
+    let mut tampered = replay.clone();
+    tampered.score += 85.0;
+    assert!(tampered.verify().is_err());
+
+    // holding nothing on the way dies long before the buttons run out
+    let mut idle = replay;
+    idle.inputs
+        .iter_mut()
+        .for_each(|input| *input = Input::default());
+    idle.score = idle.play().unwrap().score();
+    assert!(idle.verify().is_err());
+}
+
+#[test]
+fn the_game_records_one_player_runs() {
+    let mut game = DinoGame::race(9, 1);
+    while !game.is_dead() {
+        let input = Input {
+            jump: game.on_ground() || game.rising(),
+            duck: false,
+        };
+        game.step(input).unwrap();
+    }
+    let replay = game.replay().unwrap();
+    assert_eq!(replay.score, game.runs()[0].score());
+    replay.verify().unwrap();
+
+    assert!(DinoGame::race(9, 2).replay().is_none());
+}
+
+#[test]
+fn garbage_is_not_a_replay() {
+    assert!(Replay::parse("").is_err());
+    assert!(Replay::parse("dino-replay 1\nseed x\n").is_err());
+    let text = Replay::new(0, GameMode::Zen, Settings::default(), Input::default()).to_text();
+    assert!(Replay::parse(&text.replace("inputs ", "inputs 0129")).is_err());
+}
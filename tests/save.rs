@@ -0,0 +1,104 @@
+//! Checks that saves survive being stored, exported and read back by later versions.
+
+use dino::input::KeyBindings;
+use dino::mode::{DailyRecord, DailyResult, GameMode, RunResult};
+use dino::save::{SaveData, CODE_PREFIX, SAVE_VERSION};
+use dino::DinoGame;
+use egui::Key;
+use std::collections::HashMap;
+
+fn progress() -> SaveData {
+    let mut save = SaveData {
+        mode: GameMode::TimeAttack,
+        best_scores: HashMap::from([(GameMode::Classic, 8500.0), (GameMode::TimeAttack, 1700.0)]),
+        daily: DailyRecord {
+            results: vec![DailyResult {
+                day: 20000,
+                score: 4250.0,
+            }],
+        },
+        two_player: true,
+        room: "office".to_owned(),
+        ..SaveData::default()
+    };
+    save.history.record(RunResult {
+        mode: GameMode::Classic,
+        score: 8500.0,
+        assisted: false,
+    });
+    save.settings.jump_buffer = 9;
+    save
+}
+
+#[test]
+fn files_and_codes_read_back_the_same() {
+    let save = progress();
+    assert_eq!(SaveData::from_text(&save.to_text().unwrap()).unwrap(), save);
+
+    let code = save.to_code().unwrap();
+    assert!(code.starts_with(CODE_PREFIX));
+    assert!(!code.contains('\n'));
+    // pasting often picks up some whitespace
+    assert_eq!(SaveData::from_text(&format!(" {code}\n")).unwrap(), save);
+}
+
+#[test]
+fn app_state_from_before_versions_is_migrated() {
+    let old = r#"(
+        label: "Hello World!",
+        high_score: 1700.0,
+        mode: TimeAttack,
+        best_scores: {Classic: 8500.0},
+        daily: (results: [(day: 20000, score: 4250.0)]),
+        assisted_scores: {},
+        history: (runs: [(mode: Classic, score: 8500.0, assisted: false)]),
+        two_player: true,
+        bot_opponent: false,
+        relay: "ws://127.0.0.1:9001",
+        room: "office",
+        settings: (jump_buffer: 9),
+    )"#;
+    let save = SaveData::from_text(old).unwrap();
+    assert_eq!(save.version, SAVE_VERSION);
+    assert_eq!(save, progress());
+}
+
+#[test]
+fn key_bindings_are_saved() {
+    let mut save = progress();
+    save.settings.keys.jump = vec![Key::J];
+    save.settings.race_keys[1].duck = vec![Key::Enter, Key::N];
+    assert_eq!(SaveData::from_text(&save.to_text().unwrap()).unwrap(), save);
+
+    // saves from before key bindings were settings get the usual keys
+    let old = SaveData::from_text("(version: 2, settings: (jump_buffer: 9))").unwrap();
+    assert_eq!(old.settings.keys, KeyBindings::single());
+    assert_eq!(old.settings.race_keys, KeyBindings::split());
+}
+
+#[test]
+fn newer_and_damaged_saves_are_refused() {
+    let newer = progress()
+        .to_text()
+        .unwrap()
+        .replace(&format!("version: {SAVE_VERSION}"), "version: 99");
+    let err = SaveData::from_text(&newer).unwrap_err();
+    assert!(err.to_string().contains("newer"), "{err}");
+
+    assert!(SaveData::from_text("not a save").is_err());
+    assert!(SaveData::from_text(&format!("{CODE_PREFIX}!!!")).is_err());
+}
+
+#[test]
+fn the_game_keeps_everything_in_a_save() {
+    let mut game = DinoGame::default();
+    game.load_save(progress());
+    assert_eq!(game.save_data(), progress());
+
+    // the best score in the current mode is kept apart while playing, and saved with the rest
+    game.load_save(SaveData {
+        mode: GameMode::Classic,
+        ..progress()
+    });
+    assert_eq!(game.save_data().best_scores, progress().best_scores);
+}
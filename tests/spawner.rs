@@ -0,0 +1,126 @@
+//! Checks that every obstacle sequence the spawner produces can be cleared by a perfect player.
+
+use dino::input::Input;
+use dino::spawner::{JumpArc, Spawn, Spawner};
+use dino::DinoGame;
+
+const JUMP: Input = Input {
+    jump: true,
+    duck: false,
+};
+
+/// How many ticks each seed is played for.
+const TICKS: usize = 4000;
+
+/// Tries every jump timing a perfect player could use, returning whether any of them survives
+/// for `TICKS` ticks.
+///
+/// The obstacles do not depend on the player's input, so while the dino is on the ground the
+/// whole game is determined by the tick. That means only one game has to be kept per tick.
+fn survivable(seed: u64) -> bool {
+    let arc = JumpArc::full();
+    let mut grounded: Vec<Option<DinoGame>> = vec![None; TICKS + arc.total_ticks as usize + 2];
+
+    // the simulation starts in the middle of the first jump
+    let mut game = DinoGame::simulation(seed);
+    let mut tick = 0;
+    while !game.on_ground() {
+        game.step(Input::default()).unwrap();
+        tick += 1;
+    }
+    grounded[tick] = Some(game);
+
+    for tick in 0..grounded.len() {
+        let Some(game) = grounded[tick].take() else {
+            continue;
+        };
+        if tick >= TICKS {
+            return true;
+        }
+
+        let mut run = game.clone();
+        run.step(Input::default()).unwrap();
+        if !run.is_dead() {
+            grounded[tick + 1] = Some(run);
+        }
+
+        // hold jump for as long as it makes the dino rise, then let go so it can be pressed
+        // again as soon as the dino lands
+        let mut jump = game;
+        jump.step(JUMP).unwrap();
+        let mut landed = tick + 1;
+        loop {
+            let input = if jump.rising() {
+                JUMP
+            } else {
+                Input::default()
+            };
+            jump.step(input).unwrap();
+            landed += 1;
+            if jump.is_dead() || jump.on_ground() {
+                break;
+            }
+        }
+        if !jump.is_dead() && grounded[landed].is_none() {
+            grounded[landed] = Some(jump);
+        }
+    }
+    false
+}
+
+#[test]
+fn every_sequence_is_clearable() {
+    for seed in 0..32 {
+        assert!(survivable(seed), "seed {seed} cannot be cleared");
+    }
+}
+
+#[test]
+fn gaps_stay_within_reach() {
+    let dino_speed = 25.0;
+    let mut spawner = Spawner::new(7);
+    let step = dino_speed * 0.3;
+    let mut since_last = None;
+    for _ in 0..20_000 {
+        since_last = since_last.map(|gap| gap + step);
+        if let Some(Spawn::Enemy(_)) = spawner.tick(dino_speed, step) {
+            if let Some(gap) = since_last {
+                assert!(gap >= spawner.min_gap(dino_speed), "gap {gap} is too short");
+                assert!(
+                    gap <= spawner.max_gap(dino_speed) + step,
+                    "gap {gap} is too long"
+                );
+            }
+            since_last = Some(0.0);
+        }
+    }
+}
+
+#[test]
+fn slow_speeds_still_get_gaps() {
+    let fast = Spawner::new(1).min_gap(25.0);
+    for dino_speed in [0.0, 0.5, 1.0, 1.4, 2.0, 10.0] {
+        let mut spawner = Spawner::new(1);
+        let gap = spawner.min_gap(dino_speed);
+        assert!(gap.is_finite() && gap >= fast, "{dino_speed}: {gap}");
+        for _ in 0..1000 {
+            spawner.tick(dino_speed, 10.0);
+        }
+    }
+}
+
+#[test]
+fn same_seed_same_obstacles() {
+    let obstacles = |seed| {
+        let mut spawner = Spawner::new(seed);
+        (0..2000)
+            .map(|_| spawner.tick(25.0, 0.0))
+            .collect::<Vec<_>>()
+    };
+    let first = obstacles(3);
+    assert!(first.iter().any(Option::is_some));
+    for (tick, (a, b)) in first.iter().zip(obstacles(3)).enumerate() {
+        assert!(*a == b, "tick {tick}");
+    }
+    assert!(first != obstacles(4));
+}
@@ -0,0 +1,40 @@
+//! Checks that themes recolour the asset map without changing its shapes.
+
+use dino::assets::Atlas;
+use dino::raster::Raster;
+use dino::settings::Settings;
+use dino::theme::{CustomColours, Theme, ThemeKind};
+use egui::Color32;
+
+#[test]
+fn classic_leaves_the_asset_map_alone() {
+    assert_eq!(Theme::CLASSIC.recolour(Atlas::built_in().image()), None);
+    assert_eq!(Settings::default().theme(), Theme::CLASSIC);
+}
+
+#[test]
+fn ink_becomes_the_foreground() {
+    let atlas = Atlas::built_in();
+    let recoloured = Theme::HIGH_CONTRAST.recolour(atlas.image()).unwrap();
+    for (source, themed) in atlas.image().pixels().zip(recoloured.pixels()) {
+        // shapes stay the same
+        assert_eq!(source[3], themed[3]);
+        if source.0 == [83, 83, 83, 255] {
+            assert_eq!(themed.0, [255, 255, 255, 255]);
+        }
+    }
+}
+
+#[test]
+fn custom_colours_are_used() {
+    let custom = CustomColours {
+        foreground: [200, 10, 10],
+        background: [0, 0, 40],
+    };
+    let theme = Theme::new(ThemeKind::Custom, custom);
+    assert_eq!(theme.foreground, Color32::from_rgb(200, 10, 10));
+
+    let mut raster = Raster::new(0.1);
+    raster.set_theme(theme);
+    assert_eq!(raster.image().get_pixel(0, 0).0, [0, 0, 40, 255]);
+}